@@ -0,0 +1,82 @@
+//! Async example with Embassy
+//! For the Raspberry Pi Pico (rp2040), build with `--features async`
+//!
+//! Wiring:
+//!
+//! | LCD   | MCU    |
+//! |-------|--------|
+//! | SCK   | GPIO18 |
+//! | MOSI  | GPIO19 |
+//! | CS    | GPIO17 |
+//! | DC    | GPIO20 |
+//! | RESET | GPIO21 |
+//! | LED   | 3.3V   |
+//!
+//! The display is initialized with AsyncIli9341::new and shows color bars,
+//! drawn with DMA while the executor is free to run other tasks. The bars
+//! then scroll forever.
+
+#![no_main]
+#![no_std]
+
+use panic_semihosting as _;
+
+use display_interface_spi::SPIInterface;
+use embassy_executor::Spawner;
+use embassy_rp::gpio::{Level, Output};
+use embassy_rp::spi::{Config, Spi};
+use embassy_time::{Delay, Timer};
+use embedded_hal_bus::spi::ExclusiveDevice;
+use ili9341::{AsyncIli9341, DisplaySize240x320, Orientation, SPI_WRITE_MAX_HZ};
+
+/// rgb565 colors of the bars
+const BARS: [u16; 8] = [
+    0xffff, 0xffe0, 0x07ff, 0x07e0, 0xf81f, 0xf800, 0x001f, 0x0000,
+];
+
+#[embassy_executor::main]
+async fn main(_spawner: Spawner) {
+    let p = embassy_rp::init(Default::default());
+
+    let mut config = Config::default();
+    config.frequency = SPI_WRITE_MAX_HZ;
+    let spi = Spi::new_txonly(p.SPI0, p.PIN_18, p.PIN_19, p.DMA_CH0, config);
+    let cs = Output::new(p.PIN_17, Level::High);
+    let spi = ExclusiveDevice::new(spi, cs, Delay).unwrap();
+    let dc = Output::new(p.PIN_20, Level::Low);
+    let reset = Output::new(p.PIN_21, Level::High);
+    let iface = SPIInterface::new(spi, dc);
+
+    let mut display = AsyncIli9341::new(
+        iface,
+        reset,
+        |ms| Timer::after_millis(ms.into()),
+        Orientation::Portrait,
+        DisplaySize240x320,
+    )
+    .await
+    .unwrap();
+
+    let width = display.width() as u16;
+    let bar_height = display.height() as u16 / BARS.len() as u16;
+    for (i, &color) in BARS.iter().enumerate() {
+        let y0 = i as u16 * bar_height;
+        let pixels = usize::from(width) * usize::from(bar_height);
+        display
+            .draw_raw_iter(
+                0,
+                y0,
+                width - 1,
+                y0 + bar_height - 1,
+                core::iter::repeat(color).take(pixels),
+            )
+            .await
+            .unwrap();
+    }
+
+    let mut scroller = display.configure_vertical_scroll(0, 0).await.unwrap();
+    loop {
+        display.scroll_vertically(&mut scroller, 1).await.unwrap();
+        Timer::after_millis(20).await;
+    }
+}
@@ -0,0 +1,108 @@
+//! Async example with Embassy
+//! For the nRF52840 (e.g. nRF52840-DK), build with `--features async`
+//!
+//! Wiring:
+//!
+//! | LCD   | MCU   |
+//! |-------|-------|
+//! | SCK   | P0.13 |
+//! | MOSI  | P0.15 |
+//! | CS    | P0.17 |
+//! | DC    | P0.20 |
+//! | RESET | P0.22 |
+//! | LED   | 3.3V  |
+//!
+//! The display is initialized with AsyncIli9341::new, then color bars are
+//! redrawn in a loop, shifted by one bar every frame. SPI transfers are
+//! awaited, so the executor is free to run other tasks meanwhile. The frame
+//! rate is logged with defmt every second, which needs `-C link-arg=-Tdefmt.x`
+//! in the rustflags of the target.
+
+#![no_main]
+#![no_std]
+
+use defmt_rtt as _;
+use panic_semihosting as _;
+
+use display_interface::{AsyncWriteOnlyDataCommand, DisplayError};
+use display_interface_spi::SPIInterface;
+use embassy_executor::Spawner;
+use embassy_nrf::gpio::{Level, Output, OutputDrive};
+use embassy_nrf::{bind_interrupts, peripherals, spim};
+use embassy_time::{Delay, Duration, Instant, Timer};
+use embedded_hal_bus::spi::ExclusiveDevice;
+use ili9341::{AsyncIli9341, DisplaySize240x320, Orientation};
+
+bind_interrupts!(struct Irqs {
+    SPIM3 => spim::InterruptHandler<peripherals::SPI3>;
+});
+
+/// rgb565 colors of the bars
+const BARS: [u16; 8] = [
+    0xffff, 0xffe0, 0x07ff, 0x07e0, 0xf81f, 0xf800, 0x001f, 0x0000,
+];
+
+/// Draw the color bars, starting from bar `frame`
+async fn draw_frame<IFACE, RESET>(
+    display: &mut AsyncIli9341<IFACE, RESET>,
+    frame: usize,
+) -> Result<(), DisplayError>
+where
+    IFACE: AsyncWriteOnlyDataCommand,
+{
+    let width = display.width() as u16;
+    let bar_height = display.height() as u16 / BARS.len() as u16;
+    let pixels = usize::from(width) * usize::from(bar_height);
+    for i in 0..BARS.len() {
+        let color = BARS[(i + frame) % BARS.len()];
+        let y0 = i as u16 * bar_height;
+        display
+            .draw_raw_iter(
+                0,
+                y0,
+                width - 1,
+                y0 + bar_height - 1,
+                core::iter::repeat(color).take(pixels),
+            )
+            .await?;
+    }
+    Ok(())
+}
+
+#[embassy_executor::main]
+async fn main(_spawner: Spawner) {
+    let p = embassy_nrf::init(Default::default());
+
+    let mut config = spim::Config::default();
+    config.frequency = spim::Frequency::M8;
+    let spi = spim::Spim::new_txonly(p.SPI3, Irqs, p.P0_13, p.P0_15, config);
+    let cs = Output::new(p.P0_17, Level::High, OutputDrive::Standard);
+    let spi = ExclusiveDevice::new(spi, cs, Delay).unwrap();
+    let dc = Output::new(p.P0_20, Level::Low, OutputDrive::Standard);
+    let reset = Output::new(p.P0_22, Level::High, OutputDrive::Standard);
+    let iface = SPIInterface::new(spi, dc);
+
+    let mut display = AsyncIli9341::new(
+        iface,
+        reset,
+        |ms| Timer::after_millis(ms.into()),
+        Orientation::Portrait,
+        DisplaySize240x320,
+    )
+    .await
+    .unwrap();
+
+    let mut frame = 0;
+    let mut fps = 0;
+    let mut second = Instant::now();
+    loop {
+        draw_frame(&mut display, frame).await.unwrap();
+        frame = frame.wrapping_add(1);
+        fps += 1;
+        if second.elapsed() >= Duration::from_secs(1) {
+            defmt::info!("FPS: {}", fps);
+            fps = 0;
+            second = Instant::now();
+        }
+    }
+}
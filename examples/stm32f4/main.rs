@@ -0,0 +1,99 @@
+//! Standalone stm32f4xx-hal example
+//! Tested on BlackPill dev board with stm32f411ceu microcontroller
+//!
+//! Wiring:
+//!
+//! | LCD   | MCU  |
+//! |-------|------|
+//! | SCK   | PA5  |
+//! | MISO  | PA6  |
+//! | MOSI  | PA7  |
+//! | CS    | PA4  |
+//! | DC    | PA3  |
+//! | RESET | PA2  |
+//! | LED   | 3.3V |
+//!
+//! After initialization the display shows color bars, then a text
+//! is drawn on top of them with embedded-graphics.
+
+#![no_main]
+#![no_std]
+
+use panic_semihosting as _;
+
+use cortex_m_rt::entry;
+use display_interface_spi::SPIInterface;
+use embedded_graphics::{
+    mono_font::{ascii::FONT_10X20, MonoTextStyle},
+    pixelcolor::Rgb565,
+    prelude::*,
+    text::{Alignment, Text},
+};
+use ili9341::{DisplaySize240x320, Ili9341, Orientation, TestPattern};
+use stm32f4xx_hal::{pac, prelude::*, spi::Spi};
+
+#[entry]
+fn main() -> ! {
+    let dp = pac::Peripherals::take().unwrap();
+    let cp = cortex_m::Peripherals::take().unwrap();
+
+    // The BlackPill has a 25 MHz crystal, run the core at its maximum of 100 MHz
+    let rcc = dp.RCC.constrain();
+    let clocks = rcc.cfgr.use_hse(25.MHz()).sysclk(100.MHz()).freeze();
+
+    let gpioa = dp.GPIOA.split();
+
+    /*
+     *  SPI bus
+     *
+     *  The ILI9341 accepts writes with a clock of up to 10 MHz, and both
+     *  SPI MODE_0 and MODE_3 are supported.
+     */
+    let sck = gpioa.pa5.into_alternate();
+    let miso = gpioa.pa6.into_alternate();
+    let mosi = gpioa.pa7.into_alternate();
+    let spi = Spi::new(
+        dp.SPI1,
+        (sck, miso, mosi),
+        ili9341::SPI_MODE,
+        10.MHz(),
+        &clocks,
+    );
+
+    /*
+     *  Control pins
+     *
+     *  Chip select and data/command are driven by the SPI interface,
+     *  the reset pin is driven by the display driver during initialization.
+     */
+    let cs = gpioa.pa4.into_push_pull_output();
+    let dc = gpioa.pa3.into_push_pull_output();
+    let reset = gpioa.pa2.into_push_pull_output();
+
+    let iface = SPIInterface::new(spi, dc, cs);
+
+    // The driver needs a delay provider for the reset and sleep out timings
+    let mut delay = cp.SYST.delay(&clocks);
+
+    let mut lcd = Ili9341::new(
+        iface,
+        reset,
+        &mut delay,
+        Orientation::Landscape,
+        DisplaySize240x320,
+    )
+    .unwrap();
+
+    // Check the wiring and color order first
+    lcd.draw_test_pattern(TestPattern::ColorBars).unwrap();
+
+    let style = MonoTextStyle::new(&FONT_10X20, Rgb565::BLACK);
+    let center = Point::new(lcd.width() as i32 / 2, lcd.height() as i32 / 2);
+    Text::with_alignment("ILI9341 on STM32F4", center, style, Alignment::Center)
+        .draw(&mut lcd)
+        .unwrap();
+
+    loop {
+        cortex_m::asm::wfi();
+    }
+}
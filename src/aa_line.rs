@@ -0,0 +1,114 @@
+use crate::{Ili9341, Result};
+use display_interface::{DisplayError, WriteOnlyDataCommand};
+use embedded_graphics_core::pixelcolor::raw::{RawData, RawU16};
+use embedded_graphics_core::pixelcolor::{Rgb565, RgbColor};
+
+/// Largest distance from the origin accepted for the ends of a line, past
+/// any coordinate of the display
+const MAX_COORDINATE: f32 = 65535.0;
+
+impl<IFACE, RESET> Ili9341<IFACE, RESET>
+where
+    IFACE: WriteOnlyDataCommand,
+{
+    /// Draw an antialiased line from (x0, y0) to (x1, y1), clipped to the
+    /// screen, using Wu's algorithm
+    ///
+    /// For every step along the major axis the two pixels closest to the
+    /// ideal line are drawn, with an intensity proportional to their
+    /// coverage. Partially covered pixels are blended towards black rather
+    /// than towards what is already on the screen, as reading the display
+    /// memory back is slow and not supported by write-only interfaces: the
+    /// line looks best on a dark background.
+    ///
+    /// Returns [DisplayError::OutOfBoundsError] without drawing anything if
+    /// a coordinate is not finite or further than 65535 from the origin,
+    /// since the line would take forever to walk.
+    pub fn draw_aa_line(&mut self, x0: f32, y0: f32, x1: f32, y1: f32, color: Rgb565) -> Result {
+        if [x0, y0, x1, y1]
+            .iter()
+            .any(|c| !c.is_finite() || abs(*c) > MAX_COORDINATE)
+        {
+            return Err(DisplayError::OutOfBoundsError);
+        }
+        let steep = abs(y1 - y0) > abs(x1 - x0);
+        let (x0, y0, x1, y1) = if steep {
+            (y0, x0, y1, x1)
+        } else {
+            (x0, y0, x1, y1)
+        };
+        let (x0, y0, x1, y1) = if x0 > x1 {
+            (x1, y1, x0, y0)
+        } else {
+            (x0, y0, x1, y1)
+        };
+
+        let dx = x1 - x0;
+        let gradient = if dx == 0.0 { 1.0 } else { (y1 - y0) / dx };
+
+        let start = round(x0);
+        let end = round(x1);
+        let mut y = y0 + gradient * (start as f32 - x0);
+        for x in start..=end {
+            let y_floor = floor(y);
+            let coverage = y - y_floor as f32;
+            let pixels = [(y_floor, 1.0 - coverage), (y_floor + 1, coverage)];
+            for (y, intensity) in pixels {
+                let (px, py) = if steep { (y, x) } else { (x, y) };
+                let color = RawU16::from(scale(color, intensity)).into_inner();
+                self.fill_clipped(px, py, px, py, color)?;
+            }
+            y += gradient;
+        }
+        Ok(())
+    }
+}
+
+/// Scale the brightness of `color` by `intensity`, between 0 and 1
+fn scale(color: Rgb565, intensity: f32) -> Rgb565 {
+    let channel = |c: u8| (c as f32 * intensity + 0.5) as u8;
+    Rgb565::new(channel(color.r()), channel(color.g()), channel(color.b()))
+}
+
+// `f32::abs`, `f32::floor` and `f32::round` are not available in `core`
+
+fn abs(x: f32) -> f32 {
+    if x < 0.0 {
+        -x
+    } else {
+        x
+    }
+}
+
+fn floor(x: f32) -> i32 {
+    let truncated = x as i32;
+    if (truncated as f32) > x {
+        truncated - 1
+    } else {
+        truncated
+    }
+}
+
+fn round(x: f32) -> i32 {
+    floor(x + 0.5)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mock::{display, take_sent};
+    use embedded_graphics_core::pixelcolor::{Rgb565, RgbColor};
+
+    #[test]
+    fn aa_line_rejects_huge_coordinates() {
+        let mut display = display();
+        for bad in [f32::INFINITY, f32::NAN, 1e30] {
+            assert!(display
+                .draw_aa_line(0.0, 0.0, bad, 10.0, Rgb565::WHITE)
+                .is_err());
+            assert!(display
+                .draw_aa_line(-bad, 0.0, 10.0, 10.0, Rgb565::WHITE)
+                .is_err());
+        }
+        assert_eq!(take_sent(&mut display), []);
+    }
+}
@@ -0,0 +1,165 @@
+use crate::{Ili9341, Result};
+use display_interface::WriteOnlyDataCommand;
+
+/// Fixed point scale of [SIN_TABLE], 1.0 is `1 << SIN_SHIFT`
+const SIN_SHIFT: u32 = 14;
+
+/// sin(d) for every degree d, in fixed point with [SIN_SHIFT] fractional bits
+#[rustfmt::skip]
+const SIN_TABLE: [i16; 360] = [
+    0, 286, 572, 857, 1143, 1428, 1713, 1997, 2280, 2563,
+    2845, 3126, 3406, 3686, 3964, 4240, 4516, 4790, 5063, 5334,
+    5604, 5872, 6138, 6402, 6664, 6924, 7182, 7438, 7692, 7943,
+    8192, 8438, 8682, 8923, 9162, 9397, 9630, 9860, 10087, 10311,
+    10531, 10749, 10963, 11174, 11381, 11585, 11786, 11982, 12176, 12365,
+    12551, 12733, 12911, 13085, 13255, 13421, 13583, 13741, 13894, 14044,
+    14189, 14330, 14466, 14598, 14726, 14849, 14968, 15082, 15191, 15296,
+    15396, 15491, 15582, 15668, 15749, 15826, 15897, 15964, 16026, 16083,
+    16135, 16182, 16225, 16262, 16294, 16322, 16344, 16362, 16374, 16382,
+    16384, 16382, 16374, 16362, 16344, 16322, 16294, 16262, 16225, 16182,
+    16135, 16083, 16026, 15964, 15897, 15826, 15749, 15668, 15582, 15491,
+    15396, 15296, 15191, 15082, 14968, 14849, 14726, 14598, 14466, 14330,
+    14189, 14044, 13894, 13741, 13583, 13421, 13255, 13085, 12911, 12733,
+    12551, 12365, 12176, 11982, 11786, 11585, 11381, 11174, 10963, 10749,
+    10531, 10311, 10087, 9860, 9630, 9397, 9162, 8923, 8682, 8438,
+    8192, 7943, 7692, 7438, 7182, 6924, 6664, 6402, 6138, 5872,
+    5604, 5334, 5063, 4790, 4516, 4240, 3964, 3686, 3406, 3126,
+    2845, 2563, 2280, 1997, 1713, 1428, 1143, 857, 572, 286,
+    0, -286, -572, -857, -1143, -1428, -1713, -1997, -2280, -2563,
+    -2845, -3126, -3406, -3686, -3964, -4240, -4516, -4790, -5063, -5334,
+    -5604, -5872, -6138, -6402, -6664, -6924, -7182, -7438, -7692, -7943,
+    -8192, -8438, -8682, -8923, -9162, -9397, -9630, -9860, -10087, -10311,
+    -10531, -10749, -10963, -11174, -11381, -11585, -11786, -11982, -12176, -12365,
+    -12551, -12733, -12911, -13085, -13255, -13421, -13583, -13741, -13894, -14044,
+    -14189, -14330, -14466, -14598, -14726, -14849, -14968, -15082, -15191, -15296,
+    -15396, -15491, -15582, -15668, -15749, -15826, -15897, -15964, -16026, -16083,
+    -16135, -16182, -16225, -16262, -16294, -16322, -16344, -16362, -16374, -16382,
+    -16384, -16382, -16374, -16362, -16344, -16322, -16294, -16262, -16225, -16182,
+    -16135, -16083, -16026, -15964, -15897, -15826, -15749, -15668, -15582, -15491,
+    -15396, -15296, -15191, -15082, -14968, -14849, -14726, -14598, -14466, -14330,
+    -14189, -14044, -13894, -13741, -13583, -13421, -13255, -13085, -12911, -12733,
+    -12551, -12365, -12176, -11982, -11786, -11585, -11381, -11174, -10963, -10749,
+    -10531, -10311, -10087, -9860, -9630, -9397, -9162, -8923, -8682, -8438,
+    -8192, -7943, -7692, -7438, -7182, -6924, -6664, -6402, -6138, -5872,
+    -5604, -5334, -5063, -4790, -4516, -4240, -3964, -3686, -3406, -3126,
+    -2845, -2563, -2280, -1997, -1713, -1428, -1143, -857, -572, -286,
+];
+
+/// sin of `deg` degrees, any value is accepted
+fn sin(deg: u32) -> i32 {
+    i32::from(SIN_TABLE[(deg % 360) as usize])
+}
+
+/// cos of `deg` degrees, any value is accepted
+fn cos(deg: u32) -> i32 {
+    sin(deg + 90)
+}
+
+/// Degrees from `start_deg` to `end_deg` going clockwise, a full turn when
+/// they are equal
+fn sweep(start_deg: u16, end_deg: u16) -> u32 {
+    let (start, end) = (u32::from(start_deg) % 360, u32::from(end_deg) % 360);
+    match (end + 360 - start) % 360 {
+        0 => 360,
+        sweep => sweep,
+    }
+}
+
+impl<IFACE, RESET> Ili9341<IFACE, RESET>
+where
+    IFACE: WriteOnlyDataCommand,
+{
+    /// Draw an arc of the circle with center (cx, cy) and radius `r`, from
+    /// `start_deg` to `end_deg`, clipped to the screen
+    ///
+    /// Angles are in degrees, 0 pointing right and increasing clockwise; the
+    /// arc is a full circle when they are equal. Only integer fixed point
+    /// math is used, with a sine table, so this is cheap on targets without
+    /// an FPU.
+    pub fn draw_arc(
+        &mut self,
+        cx: u16,
+        cy: u16,
+        r: u16,
+        start_deg: u16,
+        end_deg: u16,
+        color: u16,
+    ) -> Result {
+        let (cx, cy, r) = (i32::from(cx), i32::from(cy), i32::from(r));
+        // Points one degree apart are more than a pixel apart beyond a radius
+        // of 57, interpolate between table entries to keep the arc connected
+        let steps = r / 57 + 1;
+        let start = u32::from(start_deg);
+        let round = 1 << (SIN_SHIFT - 1);
+        for i in 0..=sweep(start_deg, end_deg) * steps as u32 {
+            let deg = start + i / steps as u32;
+            let step = (i % steps as u32) as i32;
+            let (s0, s1) = (sin(deg), sin(deg + 1));
+            let (c0, c1) = (cos(deg), cos(deg + 1));
+            let sin = s0 + (s1 - s0) * step / steps;
+            let cos = c0 + (c1 - c0) * step / steps;
+            let x = cx + ((r * cos + round) >> SIN_SHIFT);
+            let y = cy + ((r * sin + round) >> SIN_SHIFT);
+            self.fill_clipped(x, y, x, y, color)?;
+        }
+        Ok(())
+    }
+
+    /// Draw a filled circular sector, as used in pie charts, with center
+    /// (cx, cy) and radius `r`, from `start_deg` to `end_deg`, clipped to the
+    /// screen
+    ///
+    /// Angles follow the same convention as [Ili9341::draw_arc]. The sector
+    /// is sent as horizontal spans, at most two per row.
+    pub fn draw_filled_sector(
+        &mut self,
+        cx: u16,
+        cy: u16,
+        r: u16,
+        start_deg: u16,
+        end_deg: u16,
+        color: u16,
+    ) -> Result {
+        let (cx, cy, r) = (i32::from(cx), i32::from(cy), i32::from(r));
+        let r_squared = i64::from(r) * i64::from(r);
+        let sweep = sweep(start_deg, end_deg);
+        let start = (
+            i64::from(cos(start_deg.into())),
+            i64::from(sin(start_deg.into())),
+        );
+        let end = (
+            i64::from(cos(end_deg.into())),
+            i64::from(sin(end_deg.into())),
+        );
+        // Positive when `b` is clockwise from `a` on screen, less than half a
+        // turn away
+        let cross = |a: (i64, i64), b: (i64, i64)| a.0 * b.1 - a.1 * b.0;
+        let inside = |p: (i64, i64)| {
+            if sweep == 360 {
+                true
+            } else if sweep <= 180 {
+                cross(start, p) >= 0 && cross(p, end) >= 0
+            } else {
+                !(cross(end, p) > 0 && cross(p, start) > 0)
+            }
+        };
+
+        for dy in -r..=r {
+            let mut span: Option<(i32, i32)> = None;
+            for dx in -r..=r + 1 {
+                let p = (i64::from(dx), i64::from(dy));
+                let hit = dx <= r && p.0 * p.0 + p.1 * p.1 <= r_squared && inside(p);
+                match (hit, span) {
+                    (true, None) => span = Some((dx, dx)),
+                    (true, Some((x0, _))) => span = Some((x0, dx)),
+                    (false, Some((x0, x1))) => {
+                        self.fill_clipped(cx + x0, cy + dy, cx + x1, cy + dy, color)?;
+                        span = None;
+                    }
+                    (false, None) => {}
+                }
+            }
+        }
+        Ok(())
+    }
+}
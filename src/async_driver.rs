@@ -0,0 +1,606 @@
+use crate::{
+    color, visible_rows, window_size, AddressOffset, ClippedPixels, ColorOrder, Command,
+    DisplayError, DisplaySize, Ili9341, Mode, PixelFormat, ResetTiming, Result, Scroller,
+    SLICE_CHUNK_PIXELS,
+};
+use core::future::Future;
+use core::marker::PhantomData;
+use display_interface::{AsyncWriteOnlyDataCommand, DataFormat};
+use embedded_hal::digital::OutputPin;
+#[cfg(feature = "graphics-core")]
+use {
+    crate::graphics_core::DRAW_ITER_BATCH,
+    embedded_graphics_core::{
+        pixelcolor::{raw::RawU16, Rgb565},
+        prelude::*,
+        primitives::Rectangle,
+    },
+};
+
+/// Async version of [Ili9341], for interfaces implementing
+/// [AsyncWriteOnlyDataCommand]
+///
+/// It holds exactly the same state as [Ili9341], and converting between the
+/// two with [Ili9341::into_async] and [AsyncIli9341::into_blocking] doesn't
+/// send anything to the display. This allows initializing the display with
+/// the blocking driver and switching to async drawing afterwards, or doing
+/// everything asynchronously with [AsyncIli9341::new].
+pub struct AsyncIli9341<IFACE, RESET> {
+    inner: Ili9341<IFACE, RESET>,
+}
+
+impl<IFACE, RESET> Ili9341<IFACE, RESET> {
+    /// Switch to the async driver, without sending any command
+    pub fn into_async(self) -> AsyncIli9341<IFACE, RESET> {
+        AsyncIli9341 { inner: self }
+    }
+}
+
+impl<IFACE, RESET> AsyncIli9341<IFACE, RESET> {
+    /// Switch back to the blocking driver, without sending any command
+    pub fn into_blocking(self) -> Ili9341<IFACE, RESET> {
+        self.inner
+    }
+
+    /// Get the current screen width. It can change based on the current orientation
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.inner.width
+    }
+
+    /// Get the current screen height. It can change based on the current orientation
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.inner.height
+    }
+}
+
+impl<IFACE, RESET> AsyncIli9341<IFACE, RESET>
+where
+    IFACE: AsyncWriteOnlyDataCommand,
+    RESET: OutputPin,
+{
+    /// Async version of [Ili9341::new]
+    ///
+    /// `delay` is called with a duration in milliseconds and returns a future
+    /// completing after it, which keeps this crate independent of the
+    /// executor. With Embassy:
+    ///
+    /// ```ignore
+    /// let display = AsyncIli9341::new(
+    ///     iface,
+    ///     reset,
+    ///     |ms| Timer::after_millis(ms.into()),
+    ///     Orientation::Landscape,
+    ///     DisplaySize240x320,
+    /// )
+    /// .await?;
+    /// ```
+    pub async fn new<DELAY, F, SIZE, MODE>(
+        interface: IFACE,
+        reset: RESET,
+        mut delay: DELAY,
+        mode: MODE,
+        _display_size: SIZE,
+    ) -> Result<Self>
+    where
+        DELAY: FnMut(u32) -> F,
+        F: Future<Output = ()>,
+        SIZE: DisplaySize,
+        MODE: Mode,
+    {
+        let timing = ResetTiming::DEFAULT;
+        let mut ili9341 = AsyncIli9341 {
+            inner: Ili9341 {
+                interface,
+                reset,
+                width: SIZE::WIDTH,
+                height: SIZE::HEIGHT,
+                landscape: false,
+                madctl: 0,
+                pixel_format: PixelFormat::Bits16,
+                scroll_offset: 0,
+                address_offset: AddressOffset::NONE,
+                inverted: false,
+                color_order: ColorOrder::from_madctl(mode.mode()),
+                #[cfg(feature = "trace")]
+                trace_hook: None,
+                _state: PhantomData,
+            },
+        };
+
+        let reset = &mut ili9341.inner.reset;
+        reset.set_low().map_err(|_| DisplayError::RSError)?;
+        delay(timing.reset_low_ms.into()).await;
+        reset.set_high().map_err(|_| DisplayError::RSError)?;
+        delay(timing.post_reset_ms.into()).await;
+
+        ili9341.command(Command::SoftwareReset, &[]).await?;
+        delay(timing.post_soft_reset_ms.into()).await;
+
+        ili9341.set_orientation(mode).await?;
+        ili9341
+            .command(Command::PixelFormatSet, &[PixelFormat::Bits16 as u8])
+            .await?;
+        ili9341.command(Command::SleepModeOff, &[]).await?;
+        delay(timing.sleep_out_ms.into()).await;
+        ili9341.command(Command::DisplayOn, &[]).await?;
+
+        Ok(ili9341)
+    }
+}
+
+impl<IFACE, RESET> AsyncIli9341<IFACE, RESET>
+where
+    IFACE: AsyncWriteOnlyDataCommand,
+{
+    async fn command(&mut self, cmd: Command, args: &[u8]) -> Result {
+        let interface = &mut self.inner.interface;
+        interface
+            .send_commands(DataFormat::U8(&[cmd as u8]))
+            .await?;
+        interface.send_data(DataFormat::U8(args)).await
+    }
+
+    async fn set_window(&mut self, x0: u16, y0: u16, x1: u16, y1: u16) -> Result {
+        let AddressOffset {
+            column_offset,
+            page_offset,
+        } = self.inner.address_offset;
+        let (x0, x1) = (
+            x0.saturating_add(column_offset),
+            x1.saturating_add(column_offset),
+        );
+        let (y0, y1) = (
+            y0.saturating_add(page_offset),
+            y1.saturating_add(page_offset),
+        );
+        let [x0_hi, x0_lo] = x0.to_be_bytes();
+        let [x1_hi, x1_lo] = x1.to_be_bytes();
+        self.command(Command::ColumnAddressSet, &[x0_hi, x0_lo, x1_hi, x1_lo])
+            .await?;
+        let [y0_hi, y0_lo] = y0.to_be_bytes();
+        let [y1_hi, y1_lo] = y1.to_be_bytes();
+        self.command(Command::PageAddressSet, &[y0_hi, y0_lo, y1_hi, y1_lo])
+            .await
+    }
+
+    async fn write_iter<I: IntoIterator<Item = u16>>(&mut self, data: I) -> Result {
+        self.command(Command::MemoryWrite, &[]).await?;
+        let interface = &mut self.inner.interface;
+        match self.inner.pixel_format {
+            PixelFormat::Bits16 => {
+                let mut data = data.into_iter();
+                interface.send_data(DataFormat::U16BEIter(&mut data)).await
+            }
+            PixelFormat::Bits18 => {
+                let mut data = data.into_iter().flat_map(color::rgb565_to_rgb666);
+                interface.send_data(DataFormat::U8Iter(&mut data)).await
+            }
+        }
+    }
+
+    async fn write_slices<'a, R: IntoIterator<Item = &'a [u16]>>(&mut self, rows: R) -> Result {
+        if self.inner.pixel_format == PixelFormat::Bits18 {
+            return self.write_iter(rows.into_iter().flatten().copied()).await;
+        }
+        self.command(Command::MemoryWrite, &[]).await?;
+        let mut buf = [0; SLICE_CHUNK_PIXELS];
+        for chunk in rows
+            .into_iter()
+            .flat_map(|row| row.chunks(SLICE_CHUNK_PIXELS))
+        {
+            let buf = &mut buf[..chunk.len()];
+            buf.copy_from_slice(chunk);
+            self.inner
+                .interface
+                .send_data(DataFormat::U16BE(buf))
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Async version of [Ili9341::draw_raw_iter]
+    pub async fn draw_raw_iter<I: IntoIterator<Item = u16>>(
+        &mut self,
+        x0: u16,
+        y0: u16,
+        x1: u16,
+        y1: u16,
+        data: I,
+    ) -> Result {
+        match self.inner.visible_window(x0, y0, x1, y1)? {
+            Some(visible) => {
+                self.set_window(visible.0, visible.1, visible.2, visible.3)
+                    .await?;
+                let data = ClippedPixels::new(data.into_iter(), (x0, y0, x1, y1), visible);
+                self.write_iter(data).await
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Async version of [Ili9341::draw_raw_slice]
+    pub async fn draw_raw_slice(
+        &mut self,
+        x0: u16,
+        y0: u16,
+        x1: u16,
+        y1: u16,
+        data: &[u16],
+    ) -> Result {
+        match self.inner.visible_window(x0, y0, x1, y1)? {
+            Some(visible) => {
+                self.set_window(visible.0, visible.1, visible.2, visible.3)
+                    .await?;
+                if visible == (x0, y0, x1, y1) {
+                    self.write_slices(Some(data)).await
+                } else {
+                    self.write_slices(visible_rows(data, (x0, y0, x1, y1), visible))
+                        .await
+                }
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Async version of [Ili9341::set_orientation]
+    pub async fn set_orientation<MODE: Mode>(&mut self, mode: MODE) -> Result {
+        let madctl = self.inner.color_order.apply(mode.mode());
+        self.set_address_mode(madctl, mode.is_landscape()).await
+    }
+
+    /// Async version of [Ili9341::set_address_mode]
+    pub async fn set_address_mode(&mut self, madctl: u8, swap_dimensions: bool) -> Result {
+        self.command(Command::MemoryAccessControl, &[madctl])
+            .await?;
+        self.inner.address_mode_written(madctl, swap_dimensions);
+        Ok(())
+    }
+
+    /// Async version of [Ili9341::configure_vertical_scroll]
+    pub async fn configure_vertical_scroll(
+        &mut self,
+        fixed_top_lines: u16,
+        fixed_bottom_lines: u16,
+    ) -> Result<Scroller> {
+        let height = self.inner.scroll_height();
+        let scroll_lines = height
+            .checked_sub(fixed_top_lines)
+            .and_then(|lines| lines.checked_sub(fixed_bottom_lines))
+            .ok_or(DisplayError::OutOfBoundsError)?;
+
+        let [top_hi, top_lo] = fixed_top_lines.to_be_bytes();
+        let [lines_hi, lines_lo] = scroll_lines.to_be_bytes();
+        let [bottom_hi, bottom_lo] = fixed_bottom_lines.to_be_bytes();
+        self.command(
+            Command::VerticalScrollDefine,
+            &[top_hi, top_lo, lines_hi, lines_lo, bottom_hi, bottom_lo],
+        )
+        .await?;
+
+        Ok(Scroller::new(fixed_top_lines, fixed_bottom_lines, height))
+    }
+
+    /// Async version of [Ili9341::scroll_vertically]
+    pub async fn scroll_vertically(&mut self, scroller: &mut Scroller, num_lines: u16) -> Result {
+        scroller.top_offset = scroller.advance(scroller.top_offset, num_lines.into());
+        let [offset_hi, offset_lo] = scroller.top_offset.to_be_bytes();
+        self.command(Command::VerticalScrollAddr, &[offset_hi, offset_lo])
+            .await?;
+        self.inner.scroll_offset = scroller.top_offset;
+        Ok(())
+    }
+
+    /// Async version of [Ili9341::clear_screen]
+    pub async fn clear_screen(&mut self, color: u16) -> Result {
+        let x1 = self.width().saturating_sub(1).min(u16::MAX as usize) as u16;
+        let y1 = self.height().saturating_sub(1).min(u16::MAX as usize) as u16;
+        let pixels = window_size(0, 0, x1, y1)?;
+        self.draw_raw_iter(0, 0, x1, y1, core::iter::repeat(color).take(pixels))
+            .await
+    }
+
+    /// Async version of `DrawTarget::clear`
+    #[cfg(feature = "graphics-core")]
+    pub async fn clear(&mut self, color: embedded_graphics_core::pixelcolor::Rgb565) -> Result {
+        use embedded_graphics_core::pixelcolor::raw::{RawData, RawU16};
+        self.clear_screen(RawU16::from(color).into_inner()).await
+    }
+}
+
+/// Raw drawing methods of [AsyncIli9341], for code generic over async displays
+pub trait AsyncDrawRaw {
+    /// See [Ili9341::draw_raw_iter]
+    fn draw_raw_iter<I: IntoIterator<Item = u16>>(
+        &mut self,
+        x0: u16,
+        y0: u16,
+        x1: u16,
+        y1: u16,
+        data: I,
+    ) -> impl Future<Output = Result>;
+
+    /// See [Ili9341::draw_raw_slice]
+    fn draw_raw_slice(
+        &mut self,
+        x0: u16,
+        y0: u16,
+        x1: u16,
+        y1: u16,
+        data: &[u16],
+    ) -> impl Future<Output = Result>;
+}
+
+impl<IFACE, RESET> AsyncDrawRaw for AsyncIli9341<IFACE, RESET>
+where
+    IFACE: AsyncWriteOnlyDataCommand,
+{
+    async fn draw_raw_iter<I: IntoIterator<Item = u16>>(
+        &mut self,
+        x0: u16,
+        y0: u16,
+        x1: u16,
+        y1: u16,
+        data: I,
+    ) -> Result {
+        AsyncIli9341::draw_raw_iter(self, x0, y0, x1, y1, data).await
+    }
+
+    async fn draw_raw_slice(&mut self, x0: u16, y0: u16, x1: u16, y1: u16, data: &[u16]) -> Result {
+        AsyncIli9341::draw_raw_slice(self, x0, y0, x1, y1, data).await
+    }
+}
+
+/// Async version of embedded-graphics' `DrawTarget`
+///
+/// Drawables can't draw themselves to it, as `Drawable::draw` is blocking,
+/// but their pixels can be passed to [AsyncDrawTarget::draw_iter].
+#[cfg(feature = "graphics-core")]
+pub trait AsyncDrawTarget: Dimensions {
+    type Color: PixelColor;
+    type Error;
+
+    /// See `DrawTarget::draw_iter`
+    fn draw_iter<I>(&mut self, pixels: I) -> impl Future<Output = Result<(), Self::Error>>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>;
+
+    /// See `DrawTarget::fill_contiguous`
+    fn fill_contiguous<I>(
+        &mut self,
+        area: &Rectangle,
+        colors: I,
+    ) -> impl Future<Output = Result<(), Self::Error>>
+    where
+        I: IntoIterator<Item = Self::Color>;
+
+    /// See `DrawTarget::fill_solid`
+    fn fill_solid(
+        &mut self,
+        area: &Rectangle,
+        color: Self::Color,
+    ) -> impl Future<Output = Result<(), Self::Error>>;
+
+    /// See `DrawTarget::clear`
+    fn clear(&mut self, color: Self::Color) -> impl Future<Output = Result<(), Self::Error>>;
+}
+
+#[cfg(feature = "graphics-core")]
+impl<IFACE, RESET> OriginDimensions for AsyncIli9341<IFACE, RESET> {
+    fn size(&self) -> Size {
+        self.inner.size()
+    }
+}
+
+#[cfg(feature = "graphics-core")]
+impl<IFACE, RESET> AsyncDrawTarget for AsyncIli9341<IFACE, RESET>
+where
+    IFACE: AsyncWriteOnlyDataCommand,
+{
+    type Color = Rgb565;
+    type Error = DisplayError;
+
+    /// Horizontally adjacent pixels of the same row are grouped into a
+    /// single transfer, like [Ili9341::draw_iter_batched]
+    async fn draw_iter<I>(&mut self, pixels: I) -> Result
+    where
+        I: IntoIterator<Item = Pixel<Rgb565>>,
+    {
+        let mut row = [0; DRAW_ITER_BATCH];
+        let mut len = 0;
+        let (mut x0, mut y) = (0, 0);
+
+        for Pixel(point, color) in pixels {
+            let (px, py) = match self.inner.to_display_coords(point) {
+                Some(coords) => coords,
+                None => continue,
+            };
+            let adjacent = len > 0 && py == y && usize::from(px) == usize::from(x0) + len;
+            if !adjacent || len == DRAW_ITER_BATCH {
+                self.flush_row(x0, y, &row[..len]).await?;
+                len = 0;
+                x0 = px;
+                y = py;
+            }
+            row[len] = RawU16::from(color).into_inner();
+            len += 1;
+        }
+        self.flush_row(x0, y, &row[..len]).await
+    }
+
+    async fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result
+    where
+        I: IntoIterator<Item = Rgb565>,
+    {
+        let drawable_area = area.intersection(&self.bounding_box());
+        let drawable_bottom_right = match drawable_area.bottom_right() {
+            Some(point) => point,
+            // No pixels are on screen
+            None => return Ok(()),
+        };
+        let x0 = drawable_area.top_left.x as u16;
+        let y0 = drawable_area.top_left.y as u16;
+        let x1 = drawable_bottom_right.x as u16;
+        let y1 = drawable_bottom_right.y as u16;
+        let colors = colors
+            .into_iter()
+            .map(|color| RawU16::from(color).into_inner());
+
+        if area == &drawable_area {
+            // All pixels are on screen
+            return AsyncIli9341::draw_raw_iter(self, x0, y0, x1, y1, colors).await;
+        }
+
+        // Some pixels are on screen, send them one row at a time so that
+        // only the visible part of each row is transferred
+        let width = area.size.width as usize;
+        let skip_left = (drawable_area.top_left.x - area.top_left.x) as usize;
+        let skip_top = (drawable_area.top_left.y - area.top_left.y) as usize;
+        let visible = drawable_area.size.width as usize;
+        let skip_right = width - skip_left - visible;
+
+        let mut colors = colors.skip(skip_top * width);
+        for y in y0..=y1 {
+            colors.by_ref().take(skip_left).for_each(drop);
+            let row = colors.by_ref().take(visible);
+            AsyncIli9341::draw_raw_iter(self, x0, y, x1, y, row).await?;
+            colors.by_ref().take(skip_right).for_each(drop);
+        }
+        Ok(())
+    }
+
+    async fn fill_solid(&mut self, area: &Rectangle, color: Rgb565) -> Result {
+        let pixels = area.size.width as usize * area.size.height as usize;
+        self.fill_contiguous(area, core::iter::repeat(color).take(pixels))
+            .await
+    }
+
+    async fn clear(&mut self, color: Rgb565) -> Result {
+        AsyncIli9341::clear(self, color).await
+    }
+}
+
+#[cfg(feature = "graphics-core")]
+impl<IFACE, RESET> AsyncIli9341<IFACE, RESET>
+where
+    IFACE: AsyncWriteOnlyDataCommand,
+{
+    async fn flush_row(&mut self, x0: u16, y: u16, row: &[u16]) -> Result {
+        if row.is_empty() {
+            return Ok(());
+        }
+        AsyncIli9341::draw_raw_slice(self, x0, y, x0 + row.len() as u16 - 1, y, row).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use crate::mock::{block_on, display};
+    use crate::Orientation;
+    use std::vec;
+
+    #[test]
+    #[cfg(not(feature = "bounds-check"))]
+    fn draw_raw_iter_matches_blocking() {
+        let mut blocking = display();
+        blocking.draw_raw_iter(236, 318, 241, 321, 0..24).unwrap();
+        let expected = core::mem::take(&mut blocking.interface_mut().sent);
+
+        let mut display = display().into_async();
+        block_on(display.draw_raw_iter(236, 318, 241, 321, 0..24)).unwrap();
+        let display = display.into_blocking().into_inner().0;
+        assert_eq!(display.sent, expected);
+        assert_eq!(
+            display.sent,
+            [
+                (0x2a, vec![0, 236, 0, 239]),
+                (0x2b, vec![1, 62, 1, 63]),
+                (0x2c, vec![0, 0, 0, 1, 0, 2, 0, 3, 0, 6, 0, 7, 0, 8, 0, 9]),
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "bounds-check"))]
+    fn draw_raw_iter_off_screen() {
+        let mut display = display().into_async();
+        block_on(display.draw_raw_iter(240, 0, 250, 10, 0..121)).unwrap();
+        assert_eq!(display.into_blocking().into_inner().0.sent, []);
+    }
+
+    #[test]
+    fn draw_raw_slice_matches_blocking() {
+        let frame: std::vec::Vec<u16> = (0..240 * 2).collect();
+        let mut blocking = display();
+        blocking.draw_raw_slice(0, 0, 239, 1, &frame).unwrap();
+        let expected = core::mem::take(&mut blocking.interface_mut().sent);
+
+        let mut display = display().into_async();
+        block_on(display.draw_raw_slice(0, 0, 239, 1, &frame)).unwrap();
+        let display = display.into_blocking().into_inner().0;
+        assert_eq!(display.sent, expected);
+        // One call for each window command and one per chunk of pixels
+        assert_eq!(display.data_calls, 3 + 2);
+    }
+
+    #[test]
+    #[cfg(not(feature = "bounds-check"))]
+    fn draw_raw_slice_clipped_matches_blocking() {
+        let data: std::vec::Vec<u16> = (0..24).collect();
+        let mut blocking = display();
+        blocking.draw_raw_slice(236, 318, 241, 321, &data).unwrap();
+        let expected = core::mem::take(&mut blocking.interface_mut().sent);
+
+        let mut display = display().into_async();
+        block_on(display.draw_raw_slice(236, 318, 241, 321, &data)).unwrap();
+        assert_eq!(display.into_blocking().into_inner().0.sent, expected);
+    }
+
+    #[test]
+    fn set_orientation_swaps_dimensions() {
+        let mut display = display().into_async();
+        block_on(display.set_orientation(Orientation::Landscape)).unwrap();
+        assert_eq!((display.width(), display.height()), (320, 240));
+        let display = display.into_blocking();
+        assert!(display.is_landscape());
+        assert_eq!(display.into_inner().0.sent, [(0x36, vec![0x20 | 0x08])]);
+    }
+
+    #[test]
+    #[cfg(feature = "graphics-core")]
+    fn draw_target_matches_blocking() {
+        use super::AsyncDrawTarget;
+        use embedded_graphics_core::{
+            pixelcolor::{raw::RawU16, Rgb565},
+            prelude::*,
+            primitives::Rectangle,
+        };
+
+        let area = Rectangle::new(Point::new(-2, -1), Size::new(5, 3));
+        let colors = || (0..15).map(|i| Rgb565::from(RawU16::new(i)));
+        let pixels = || {
+            [
+                Pixel(Point::new(1, 1), Rgb565::RED),
+                Pixel(Point::new(2, 1), Rgb565::BLUE),
+            ]
+        };
+
+        let mut blocking = display();
+        blocking.fill_contiguous(&area, colors()).unwrap();
+        blocking.draw_iter(pixels()).unwrap();
+        let expected = core::mem::take(&mut blocking.interface_mut().sent);
+
+        let mut display = display().into_async();
+        block_on(AsyncDrawTarget::fill_contiguous(
+            &mut display,
+            &area,
+            colors(),
+        ))
+        .unwrap();
+        block_on(AsyncDrawTarget::draw_iter(&mut display, pixels())).unwrap();
+        assert_eq!(display.into_blocking().into_inner().0.sent, expected);
+    }
+}
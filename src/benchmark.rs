@@ -0,0 +1,109 @@
+use display_interface::{DataFormat, DisplayError, WriteOnlyDataCommand};
+
+/// Interface wrapper that counts how many bytes are sent to the display
+///
+/// Wrap the real interface before passing it to [Ili9341::new](crate::Ili9341::new),
+/// then read the counters through [Ili9341::interface](crate::Ili9341::interface)
+/// to measure the cost of a rendering operation:
+///
+/// ```ignore
+/// let mut display = Ili9341::new(BenchmarkInterface::new(iface), reset, &mut delay, mode, size)?;
+/// display.interface_mut().reset_counters();
+/// display.clear(Rgb565::BLACK)?;
+/// let bytes = display.interface().bytes_sent();
+/// ```
+pub struct BenchmarkInterface<IFACE> {
+    inner: IFACE,
+    bytes_sent: u64,
+    commands_sent: u32,
+}
+
+impl<IFACE> BenchmarkInterface<IFACE> {
+    pub fn new(inner: IFACE) -> Self {
+        BenchmarkInterface {
+            inner,
+            bytes_sent: 0,
+            commands_sent: 0,
+        }
+    }
+
+    /// Total number of command and data bytes sent since the last reset
+    pub fn bytes_sent(&self) -> u64 {
+        self.bytes_sent
+    }
+
+    /// Number of command bytes sent since the last reset
+    pub fn commands_sent(&self) -> u32 {
+        self.commands_sent
+    }
+
+    pub fn reset_counters(&mut self) {
+        self.bytes_sent = 0;
+        self.commands_sent = 0;
+    }
+
+    /// Release the wrapped interface
+    pub fn into_inner(self) -> IFACE {
+        self.inner
+    }
+}
+
+/// Forward `data` to `send`, returning how many bytes it contained
+fn counted<F>(data: DataFormat<'_>, send: F) -> (u64, Result<(), DisplayError>)
+where
+    F: FnOnce(DataFormat<'_>) -> Result<(), DisplayError>,
+{
+    let mut items = 0u64;
+    let (bytes_per_item, result) = match data {
+        DataFormat::U8(buf) => {
+            items = buf.len() as u64;
+            (1, send(DataFormat::U8(buf)))
+        }
+        DataFormat::U16(buf) => {
+            items = buf.len() as u64;
+            (2, send(DataFormat::U16(buf)))
+        }
+        DataFormat::U16BE(buf) => {
+            items = buf.len() as u64;
+            (2, send(DataFormat::U16BE(buf)))
+        }
+        DataFormat::U16LE(buf) => {
+            items = buf.len() as u64;
+            (2, send(DataFormat::U16LE(buf)))
+        }
+        DataFormat::U8Iter(iter) => {
+            let mut iter = iter.inspect(|_| items += 1);
+            (1, send(DataFormat::U8Iter(&mut iter)))
+        }
+        DataFormat::U16BEIter(iter) => {
+            let mut iter = iter.inspect(|_| items += 1);
+            (2, send(DataFormat::U16BEIter(&mut iter)))
+        }
+        DataFormat::U16LEIter(iter) => {
+            let mut iter = iter.inspect(|_| items += 1);
+            (2, send(DataFormat::U16LEIter(&mut iter)))
+        }
+        _ => (0, Err(DisplayError::DataFormatNotImplemented)),
+    };
+    (items * bytes_per_item, result)
+}
+
+impl<IFACE> WriteOnlyDataCommand for BenchmarkInterface<IFACE>
+where
+    IFACE: WriteOnlyDataCommand,
+{
+    fn send_commands(&mut self, cmd: DataFormat<'_>) -> Result<(), DisplayError> {
+        let inner = &mut self.inner;
+        let (bytes, result) = counted(cmd, |cmd| inner.send_commands(cmd));
+        self.bytes_sent += bytes;
+        self.commands_sent += bytes as u32;
+        result
+    }
+
+    fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        let inner = &mut self.inner;
+        let (bytes, result) = counted(buf, |buf| inner.send_data(buf));
+        self.bytes_sent += bytes;
+        result
+    }
+}
@@ -0,0 +1,111 @@
+use core::ops::Deref;
+
+use crate::color::rgb888_to_rgb565;
+use crate::{DisplayError, Ili9341, Result};
+use display_interface::WriteOnlyDataCommand;
+
+/// Palette of 256 rgb565 colors for [Ili9341::draw_bitmap_8bpp]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PaletteU16(pub [u16; 256]);
+
+impl PaletteU16 {
+    /// Convert a palette of 8 bit per component colors
+    pub fn from_rgb888(colors: &[(u8, u8, u8); 256]) -> Self {
+        let mut palette = [0; 256];
+        for (entry, &(r, g, b)) in palette.iter_mut().zip(colors.iter()) {
+            *entry = rgb888_to_rgb565(r, g, b);
+        }
+        PaletteU16(palette)
+    }
+}
+
+impl Deref for PaletteU16 {
+    type Target = [u16; 256];
+
+    fn deref(&self) -> &[u16; 256] {
+        &self.0
+    }
+}
+
+/// Bottom-right corner of a `width` x `height` bitmap with its top-left
+/// corner at (x0, y0), or `None` if the bitmap is empty
+fn bitmap_corner(x0: u16, y0: u16, width: u16, height: u16) -> Result<Option<(u16, u16)>> {
+    if width == 0 || height == 0 {
+        return Ok(None);
+    }
+    let x1 = x0
+        .checked_add(width - 1)
+        .ok_or(DisplayError::OutOfBoundsError)?;
+    let y1 = y0
+        .checked_add(height - 1)
+        .ok_or(DisplayError::OutOfBoundsError)?;
+    Ok(Some((x1, y1)))
+}
+
+impl<IFACE, RESET> Ili9341<IFACE, RESET>
+where
+    IFACE: WriteOnlyDataCommand,
+{
+    /// Draw a `width` x `height` bitmap of 4 bits per pixel indexes into
+    /// `palette`, with its top-left corner at (x0, y0)
+    ///
+    /// Each byte holds two pixels, the left one in the high nibble. Rows
+    /// start on a new byte, so with an odd width the low nibble of the last
+    /// byte of each row is ignored. Returns [DisplayError::InvalidFormatError]
+    /// if `data` is not exactly `(width + 1) / 2 * height` bytes.
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_bitmap_4bpp(
+        &mut self,
+        x0: u16,
+        y0: u16,
+        width: u16,
+        height: u16,
+        data: &[u8],
+        palette: &[u16; 16],
+    ) -> Result {
+        let row_bytes = usize::from(width).div_ceil(2);
+        if data.len() != row_bytes * usize::from(height) {
+            return Err(DisplayError::InvalidFormatError);
+        }
+        let (x1, y1) = match bitmap_corner(x0, y0, width, height)? {
+            Some(corner) => corner,
+            None => return Ok(()),
+        };
+
+        let pixels = data.chunks_exact(row_bytes).flat_map(|row| {
+            row.iter()
+                .flat_map(|&byte| [byte >> 4, byte & 0x0f])
+                .take(usize::from(width))
+                .map(|index| palette[usize::from(index)])
+        });
+        self.draw_raw_iter(x0, y0, x1, y1, pixels)
+    }
+
+    /// Draw a `width` x `height` bitmap of 8 bits per pixel indexes into
+    /// `palette`, with its top-left corner at (x0, y0)
+    ///
+    /// A [PaletteU16] can be passed as `palette`. Returns
+    /// [DisplayError::InvalidFormatError] if `data` is not exactly
+    /// `width * height` bytes.
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_bitmap_8bpp(
+        &mut self,
+        x0: u16,
+        y0: u16,
+        width: u16,
+        height: u16,
+        data: &[u8],
+        palette: &[u16; 256],
+    ) -> Result {
+        if data.len() != usize::from(width) * usize::from(height) {
+            return Err(DisplayError::InvalidFormatError);
+        }
+        let (x1, y1) = match bitmap_corner(x0, y0, width, height)? {
+            Some(corner) => corner,
+            None => return Ok(()),
+        };
+
+        let pixels = data.iter().map(|&index| palette[usize::from(index)]);
+        self.draw_raw_iter(x0, y0, x1, y1, pixels)
+    }
+}
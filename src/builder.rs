@@ -0,0 +1,339 @@
+use core::marker::PhantomData;
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::OutputPin;
+
+use display_interface::WriteOnlyDataCommand;
+
+use crate::{
+    ColorOrder, Command, CustomMode, DisplayError, DisplaySize, DisplaySize240x320, Ili9341, Mode,
+    ModeState, PixelFormat, PowerConfig, Result,
+};
+
+/// Delays applied while resetting the display during initialization
+///
+/// Some panels with large capacitors on their supply or reset lines need
+/// longer delays than the ones required by the datasheet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ResetTiming {
+    /// How long the reset pin is held low
+    pub reset_low_ms: u16,
+    /// Wait after releasing the reset pin, before the software reset
+    pub post_reset_ms: u16,
+    /// Wait after the software reset, before Sleep Out
+    pub post_soft_reset_ms: u16,
+    /// Wait after Sleep Out, before turning the display on
+    pub sleep_out_ms: u16,
+}
+
+impl ResetTiming {
+    /// The timings used by [Ili9341::new]
+    pub const DEFAULT: ResetTiming = ResetTiming {
+        reset_low_ms: 1,
+        post_reset_ms: 5,
+        post_soft_reset_ms: 120,
+        sleep_out_ms: 5,
+    };
+}
+
+impl Default for ResetTiming {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Offset between the controller addresses and the first visible pixel
+///
+/// Some modules use a panel smaller than the memory of the controller, and
+/// don't wire it starting at address 0. The offset is added to every window
+/// sent to the display.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AddressOffset {
+    pub column_offset: u16,
+    pub page_offset: u16,
+}
+
+impl AddressOffset {
+    /// No offset, the default
+    pub const NONE: AddressOffset = AddressOffset {
+        column_offset: 0,
+        page_offset: 0,
+    };
+}
+
+/// Builder for [Ili9341], for when the defaults of [Ili9341::new] need to be changed
+///
+/// ```ignore
+/// let display = Ili9341Builder::new(iface, reset)
+///     .with_orientation(Orientation::Portrait)
+///     .with_reset_timing(ResetTiming {
+///         reset_low_ms: 10,
+///         ..ResetTiming::DEFAULT
+///     })
+///     .build(&mut delay)?;
+/// ```
+///
+/// By default the display is configured as [DisplaySize240x320] in
+/// landscape. Setting a size with [Ili9341Builder::with_display_size]
+/// switches to the [DisplaySize::default_mode] of that size instead.
+pub struct Ili9341Builder<IFACE, RESET> {
+    interface: IFACE,
+    reset: RESET,
+    /// Mode set with [Ili9341Builder::with_orientation]
+    mode: Option<CustomMode>,
+    /// Mode used when no mode was set: landscape, or the default mode of the
+    /// size set with [Ili9341Builder::with_display_size]
+    default_mode: CustomMode,
+    width: usize,
+    height: usize,
+    reset_timing: ResetTiming,
+    address_offset: AddressOffset,
+    power_config: Option<PowerConfig>,
+    color_order: Option<ColorOrder>,
+}
+
+impl<IFACE, RESET> Ili9341Builder<IFACE, RESET> {
+    pub fn new(interface: IFACE, reset: RESET) -> Self {
+        Ili9341Builder {
+            interface,
+            reset,
+            mode: None,
+            default_mode: CustomMode::landscape(),
+            width: DisplaySize240x320::WIDTH,
+            height: DisplaySize240x320::HEIGHT,
+            reset_timing: ResetTiming::DEFAULT,
+            address_offset: AddressOffset::NONE,
+            power_config: None,
+            color_order: None,
+        }
+    }
+
+    /// Replace the display interface
+    pub fn with_interface<IFACE2>(self, interface: IFACE2) -> Ili9341Builder<IFACE2, RESET> {
+        Ili9341Builder {
+            interface,
+            reset: self.reset,
+            mode: self.mode,
+            default_mode: self.default_mode,
+            width: self.width,
+            height: self.height,
+            reset_timing: self.reset_timing,
+            address_offset: self.address_offset,
+            power_config: self.power_config,
+            color_order: self.color_order,
+        }
+    }
+
+    /// Replace the reset pin
+    pub fn with_reset<RESET2>(self, reset: RESET2) -> Ili9341Builder<IFACE, RESET2> {
+        Ili9341Builder {
+            interface: self.interface,
+            reset,
+            mode: self.mode,
+            default_mode: self.default_mode,
+            width: self.width,
+            height: self.height,
+            reset_timing: self.reset_timing,
+            address_offset: self.address_offset,
+            power_config: self.power_config,
+            color_order: self.color_order,
+        }
+    }
+
+    pub fn with_orientation<MODE: Mode>(mut self, mode: MODE) -> Self {
+        self.mode = Some(CustomMode {
+            madctl_byte: mode.mode(),
+            is_landscape: mode.is_landscape(),
+        });
+        self
+    }
+
+    /// Set the size of the display, and use its [DisplaySize::default_mode]
+    /// unless a mode is set with [Ili9341Builder::with_orientation]
+    pub fn with_display_size<SIZE: DisplaySize>(mut self, _display_size: SIZE) -> Self {
+        self.width = SIZE::WIDTH;
+        self.height = SIZE::HEIGHT;
+        self.default_mode = SIZE::default_mode();
+        self
+    }
+
+    pub fn with_reset_timing(mut self, reset_timing: ResetTiming) -> Self {
+        self.reset_timing = reset_timing;
+        self
+    }
+
+    /// Set how long the reset pin is held low, see
+    /// [ResetTiming::reset_low_ms]
+    pub fn with_reset_delay_ms(mut self, ms: u16) -> Self {
+        self.reset_timing.reset_low_ms = ms;
+        self
+    }
+
+    /// Set the wait after Sleep Out, see [ResetTiming::sleep_out_ms]
+    pub fn with_sleep_out_delay_ms(mut self, ms: u16) -> Self {
+        self.reset_timing.sleep_out_ms = ms;
+        self
+    }
+
+    pub fn with_address_offset(mut self, address_offset: AddressOffset) -> Self {
+        self.address_offset = address_offset;
+        self
+    }
+
+    /// Override the color order selected by the orientation, for panels
+    /// wired differently, see [ColorOrder]
+    pub fn with_color_order(mut self, color_order: ColorOrder) -> Self {
+        self.color_order = Some(color_order);
+        self
+    }
+
+    /// Set the power control registers during initialization, instead of
+    /// keeping their reset values
+    pub fn with_power_config(mut self, power_config: PowerConfig) -> Self {
+        self.power_config = Some(power_config);
+        self
+    }
+}
+
+/// Start from the parts returned by [Ili9341::into_parts], with the default
+/// configuration
+impl<IFACE, RESET> From<(IFACE, RESET)> for Ili9341Builder<IFACE, RESET>
+where
+    IFACE: WriteOnlyDataCommand,
+    RESET: OutputPin,
+{
+    fn from((interface, reset): (IFACE, RESET)) -> Self {
+        Self::new(interface, reset)
+    }
+}
+
+impl<IFACE, RESET> Ili9341Builder<IFACE, RESET>
+where
+    IFACE: WriteOnlyDataCommand,
+    RESET: OutputPin,
+{
+    /// Reset and initialize the display
+    pub fn build<DELAY: DelayNs>(self, delay: &mut DELAY) -> Result<Ili9341<IFACE, RESET>> {
+        let timing = self.reset_timing;
+        let mode = self.mode.unwrap_or(self.default_mode);
+        let color_order = self
+            .color_order
+            .unwrap_or_else(|| ColorOrder::from_madctl(mode.madctl_byte));
+        let mut ili9341 = Ili9341 {
+            interface: self.interface,
+            reset: self.reset,
+            width: self.width,
+            height: self.height,
+            landscape: false,
+            madctl: 0,
+            pixel_format: PixelFormat::Bits16,
+            scroll_offset: 0,
+            address_offset: self.address_offset,
+            inverted: false,
+            color_order,
+            #[cfg(feature = "trace")]
+            trace_hook: None,
+            _state: PhantomData,
+        };
+
+        // Do hardware reset by holding reset low for at least 10us
+        ili9341.reset.set_low().map_err(|_| DisplayError::RSError)?;
+        delay.delay_ms(timing.reset_low_ms.into());
+        // Set high for normal operation
+        ili9341
+            .reset
+            .set_high()
+            .map_err(|_| DisplayError::RSError)?;
+
+        // Wait 5ms after reset before sending commands
+        // and 120ms before sending Sleep Out
+        delay.delay_ms(timing.post_reset_ms.into());
+
+        // Do software reset
+        ili9341.command(Command::SoftwareReset, &[])?;
+
+        // Wait 5ms after reset before sending commands
+        // and 120ms before sending Sleep Out
+        delay.delay_ms(timing.post_soft_reset_ms.into());
+
+        ili9341.set_orientation(mode)?;
+
+        // Set pixel format to 16 bits per pixel
+        ili9341.set_pixel_format(PixelFormat::Bits16)?;
+
+        if let Some(power_config) = self.power_config {
+            ili9341.set_power_config(power_config)?;
+        }
+
+        ili9341.command(Command::SleepModeOff, &[])?;
+
+        // Wait 5ms after Sleep Out before sending commands
+        delay.delay_ms(timing.sleep_out_ms.into());
+
+        ili9341.display_mode(ModeState::On)?;
+
+        Ok(ili9341)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::Ili9341Builder;
+    use crate::mock::{MockDelay, MockInterface, MockPin};
+    use crate::{CustomMode, DisplaySize, DisplaySize240x320, DisplaySize320x480, Orientation};
+    use std::vec;
+    use std::vec::Vec;
+
+    /// MADCTL values sent during initialization, and the resulting width
+    fn madctl(builder: Ili9341Builder<MockInterface, MockPin>) -> (Vec<u8>, usize) {
+        let mut display = builder.build(&mut MockDelay).unwrap();
+        let width = display.width();
+        let sent = &display.interface_mut().sent;
+        let madctl = sent
+            .iter()
+            .filter(|(cmd, _)| *cmd == 0x36)
+            .flat_map(|(_, data)| data.iter().copied())
+            .collect();
+        (madctl, width)
+    }
+
+    struct LandscapePanel;
+
+    impl DisplaySize for LandscapePanel {
+        const WIDTH: usize = 240;
+        const HEIGHT: usize = 320;
+
+        fn default_mode() -> CustomMode {
+            CustomMode::landscape()
+        }
+    }
+
+    #[test]
+    fn default_mode_from_size() {
+        let builder = || Ili9341Builder::new(MockInterface::default(), MockPin);
+        assert_eq!(madctl(builder()), (vec![0x28], 320));
+        assert_eq!(
+            madctl(builder().with_display_size(DisplaySize240x320)),
+            (vec![0x48], 240)
+        );
+        assert_eq!(
+            madctl(builder().with_display_size(DisplaySize320x480)),
+            (vec![0x48], 320)
+        );
+        assert_eq!(
+            madctl(builder().with_display_size(LandscapePanel)),
+            (vec![0x28], 320)
+        );
+        // An explicit orientation wins, whatever the order
+        assert_eq!(
+            madctl(
+                builder()
+                    .with_orientation(Orientation::Portrait)
+                    .with_display_size(LandscapePanel)
+            ),
+            (vec![0x48], 240)
+        );
+    }
+}
@@ -0,0 +1,132 @@
+//! Conversions between rgb565 and other color formats
+//!
+//! Components are expanded by replicating their most significant bits,
+//! so that full intensity in one format maps to full intensity in the other.
+
+/// Convert 8 bit components to a rgb565 value
+pub fn rgb888_to_rgb565(r: u8, g: u8, b: u8) -> u16 {
+    (u16::from(r >> 3) << 11) | (u16::from(g >> 2) << 5) | u16::from(b >> 3)
+}
+
+/// Convert a rgb332 value (3 bits red, 3 bits green, 2 bits blue) to rgb565
+pub fn rgb332_to_rgb565(v: u8) -> u16 {
+    let r = u16::from(v >> 5);
+    let g = u16::from((v >> 2) & 0x07);
+    let b = u16::from(v & 0x03);
+    let r = (r << 2) | (r >> 1);
+    let g = (g << 3) | g;
+    let b = (b << 3) | (b << 1) | (b >> 1);
+    (r << 11) | (g << 5) | b
+}
+
+/// Split a rgb565 value into 8 bit components
+pub fn rgb565_to_rgb888(color: u16) -> (u8, u8, u8) {
+    let r = ((color >> 11) & 0x1f) as u8;
+    let g = ((color >> 5) & 0x3f) as u8;
+    let b = (color & 0x1f) as u8;
+    (
+        (r << 3) | (r >> 2),
+        (g << 2) | (g >> 4),
+        (b << 3) | (b >> 2),
+    )
+}
+
+/// Linear interpolation between two rgb565 values, from `from` when `t` is 0
+/// to `to` when `t` is 255
+pub fn blend_rgb565(from: u16, to: u16, t: u8) -> u16 {
+    let mix = |shift: u16, mask: u16| {
+        let a = i32::from((from >> shift) & mask);
+        let b = i32::from((to >> shift) & mask);
+        ((a + (b - a) * i32::from(t) / 255) as u16) << shift
+    };
+    mix(11, 0x1f) | mix(5, 0x3f) | mix(0, 0x1f)
+}
+
+/// Perceived brightness of a rgb565 value, from 0 to 255
+///
+/// Uses the ITU-R BT.601 weights in 8 bit fixed point.
+pub fn luminance_rgb565(color: u16) -> u8 {
+    let (r, g, b) = rgb565_to_rgb888(color);
+    ((77 * u16::from(r) + 150 * u16::from(g) + 29 * u16::from(b)) >> 8) as u8
+}
+
+/// Expand a rgb565 value to the 3 bytes of the 18 bits per pixel format,
+/// with each component left-aligned in its byte
+pub(crate) fn rgb565_to_rgb666(color: u16) -> [u8; 3] {
+    let r = (color >> 11) & 0x1f;
+    let g = (color >> 5) & 0x3f;
+    let b = color & 0x1f;
+    [
+        (((r << 1) | (r >> 4)) << 2) as u8,
+        (g << 2) as u8,
+        (((b << 1) | (b >> 4)) << 2) as u8,
+    ]
+}
+
+/// Convert the 3 bytes of a pixel read from the display memory,
+/// with each component left-aligned in its byte, to rgb565
+pub(crate) fn rgb666_to_rgb565(r: u8, g: u8, b: u8) -> u16 {
+    rgb888_to_rgb565(r, g, b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rgb565_round_trip() {
+        for color in 0..=u16::MAX {
+            let (r, g, b) = rgb565_to_rgb888(color);
+            assert_eq!(rgb888_to_rgb565(r, g, b), color);
+        }
+    }
+
+    #[test]
+    fn rgb888_round_trip() {
+        for v in 0..=u8::MAX {
+            let (r, g, b) = rgb565_to_rgb888(rgb888_to_rgb565(v, v, v));
+            assert!(r.abs_diff(v) < 8, "red {:#04x} -> {:#04x}", v, r);
+            assert!(g.abs_diff(v) < 4, "green {:#04x} -> {:#04x}", v, g);
+            assert!(b.abs_diff(v) < 8, "blue {:#04x} -> {:#04x}", v, b);
+        }
+    }
+
+    #[test]
+    fn rgb888_extremes() {
+        assert_eq!(rgb888_to_rgb565(0x00, 0x00, 0x00), 0x0000);
+        assert_eq!(rgb888_to_rgb565(0xff, 0xff, 0xff), 0xffff);
+        assert_eq!(rgb888_to_rgb565(0xff, 0x00, 0x00), 0xf800);
+        assert_eq!(rgb888_to_rgb565(0x00, 0xff, 0x00), 0x07e0);
+        assert_eq!(rgb888_to_rgb565(0x00, 0x00, 0xff), 0x001f);
+
+        assert_eq!(rgb565_to_rgb888(0x0000), (0x00, 0x00, 0x00));
+        assert_eq!(rgb565_to_rgb888(0xffff), (0xff, 0xff, 0xff));
+        assert_eq!(rgb565_to_rgb888(0xf800), (0xff, 0x00, 0x00));
+        assert_eq!(rgb565_to_rgb888(0x07e0), (0x00, 0xff, 0x00));
+        assert_eq!(rgb565_to_rgb888(0x001f), (0x00, 0x00, 0xff));
+    }
+
+    #[test]
+    fn rgb332_extremes() {
+        assert_eq!(rgb332_to_rgb565(0x00), 0x0000);
+        assert_eq!(rgb332_to_rgb565(0xff), 0xffff);
+        assert_eq!(rgb332_to_rgb565(0xe0), 0xf800);
+        assert_eq!(rgb332_to_rgb565(0x1c), 0x07e0);
+        assert_eq!(rgb332_to_rgb565(0x03), 0x001f);
+    }
+
+    #[test]
+    fn luminance() {
+        assert_eq!(luminance_rgb565(0x0000), 0);
+        assert_eq!(luminance_rgb565(0xffff), 255);
+        // The BT.601 weights, scaled to 256
+        assert_eq!(luminance_rgb565(0xf800), 76);
+        assert_eq!(luminance_rgb565(0x07e0), 149);
+        assert_eq!(luminance_rgb565(0x001f), 28);
+        for color in 0..=u16::MAX {
+            let (r, g, b) = rgb565_to_rgb888(color);
+            let max = r.max(g).max(b);
+            assert!(luminance_rgb565(color) <= max);
+        }
+    }
+}
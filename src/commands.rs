@@ -0,0 +1,179 @@
+//! Command bytes documented in the ILI9341 datasheet
+//!
+//! These can be sent with [Ili9341::write_command_raw](crate::Ili9341::write_command_raw)
+//! to access registers the driver does not wrap yet.
+
+// Level 1 commands
+
+/// No operation
+pub const NOP: u8 = 0x00;
+/// Software reset
+pub const SOFTWARE_RESET: u8 = 0x01;
+/// Read display identification information
+pub const READ_DISPLAY_ID: u8 = 0x04;
+/// Read display status
+pub const READ_DISPLAY_STATUS: u8 = 0x09;
+/// Read display power mode
+pub const READ_DISPLAY_POWER_MODE: u8 = 0x0a;
+/// Read display MADCTL
+pub const READ_DISPLAY_MADCTL: u8 = 0x0b;
+/// Read display pixel format
+pub const READ_DISPLAY_PIXEL_FORMAT: u8 = 0x0c;
+/// Read display image format
+pub const READ_DISPLAY_IMAGE_FORMAT: u8 = 0x0d;
+/// Read display signal mode
+pub const READ_DISPLAY_SIGNAL_MODE: u8 = 0x0e;
+/// Read display self-diagnostic result
+pub const READ_DISPLAY_SELF_DIAGNOSTIC: u8 = 0x0f;
+/// Enter sleep mode
+pub const SLEEP_IN: u8 = 0x10;
+/// Sleep out
+pub const SLEEP_OUT: u8 = 0x11;
+/// Partial mode on
+pub const PARTIAL_MODE_ON: u8 = 0x12;
+/// Normal display mode on
+pub const NORMAL_DISPLAY_MODE_ON: u8 = 0x13;
+/// Display inversion off
+pub const DISPLAY_INVERSION_OFF: u8 = 0x20;
+/// Display inversion on
+pub const DISPLAY_INVERSION_ON: u8 = 0x21;
+/// Gamma set
+pub const GAMMA_SET: u8 = 0x26;
+/// Display off
+pub const DISPLAY_OFF: u8 = 0x28;
+/// Display on
+pub const DISPLAY_ON: u8 = 0x29;
+/// Column address set
+pub const COLUMN_ADDRESS_SET: u8 = 0x2a;
+/// Page address set
+pub const PAGE_ADDRESS_SET: u8 = 0x2b;
+/// Memory write
+pub const MEMORY_WRITE: u8 = 0x2c;
+/// Color set (lookup table for 16 to 18 bit color conversion)
+pub const COLOR_SET: u8 = 0x2d;
+/// Memory read
+pub const MEMORY_READ: u8 = 0x2e;
+/// Partial area
+pub const PARTIAL_AREA: u8 = 0x30;
+/// Vertical scrolling definition
+pub const VERTICAL_SCROLLING_DEFINITION: u8 = 0x33;
+/// Tearing effect line off
+pub const TEARING_EFFECT_LINE_OFF: u8 = 0x34;
+/// Tearing effect line on
+pub const TEARING_EFFECT_LINE_ON: u8 = 0x35;
+/// Memory access control (MADCTL)
+pub const MEMORY_ACCESS_CONTROL: u8 = 0x36;
+/// Vertical scrolling start address
+pub const VERTICAL_SCROLLING_START_ADDRESS: u8 = 0x37;
+/// Idle mode off
+pub const IDLE_MODE_OFF: u8 = 0x38;
+/// Idle mode on
+pub const IDLE_MODE_ON: u8 = 0x39;
+/// Pixel format set (COLMOD)
+pub const PIXEL_FORMAT_SET: u8 = 0x3a;
+/// Write memory continue
+pub const WRITE_MEMORY_CONTINUE: u8 = 0x3c;
+/// Read memory continue
+pub const READ_MEMORY_CONTINUE: u8 = 0x3e;
+/// Set tear scanline
+pub const SET_TEAR_SCANLINE: u8 = 0x44;
+/// Get scanline
+pub const GET_SCANLINE: u8 = 0x45;
+/// Write display brightness
+pub const WRITE_DISPLAY_BRIGHTNESS: u8 = 0x51;
+/// Read display brightness
+pub const READ_DISPLAY_BRIGHTNESS: u8 = 0x52;
+/// Write CTRL display
+pub const WRITE_CTRL_DISPLAY: u8 = 0x53;
+/// Read CTRL display
+pub const READ_CTRL_DISPLAY: u8 = 0x54;
+/// Write content adaptive brightness control
+pub const WRITE_CABC: u8 = 0x55;
+/// Read content adaptive brightness control
+pub const READ_CABC: u8 = 0x56;
+/// Write CABC minimum brightness
+pub const WRITE_CABC_MINIMUM_BRIGHTNESS: u8 = 0x5e;
+/// Read CABC minimum brightness
+pub const READ_CABC_MINIMUM_BRIGHTNESS: u8 = 0x5f;
+/// Read ID1
+pub const READ_ID1: u8 = 0xda;
+/// Read ID2
+pub const READ_ID2: u8 = 0xdb;
+/// Read ID3
+pub const READ_ID3: u8 = 0xdc;
+
+// Level 2 commands
+
+/// RGB interface signal control
+pub const RGB_INTERFACE_SIGNAL_CONTROL: u8 = 0xb0;
+/// Frame rate control in normal mode (full colors)
+pub const FRAME_RATE_CONTROL_NORMAL: u8 = 0xb1;
+/// Frame rate control in idle mode (8 colors)
+pub const FRAME_RATE_CONTROL_IDLE: u8 = 0xb2;
+/// Frame rate control in partial mode (full colors)
+pub const FRAME_RATE_CONTROL_PARTIAL: u8 = 0xb3;
+/// Display inversion control
+pub const DISPLAY_INVERSION_CONTROL: u8 = 0xb4;
+/// Blanking porch control
+pub const BLANKING_PORCH_CONTROL: u8 = 0xb5;
+/// Display function control
+pub const DISPLAY_FUNCTION_CONTROL: u8 = 0xb6;
+/// Entry mode set
+pub const ENTRY_MODE_SET: u8 = 0xb7;
+/// Backlight control 1
+pub const BACKLIGHT_CONTROL_1: u8 = 0xb8;
+/// Backlight control 2
+pub const BACKLIGHT_CONTROL_2: u8 = 0xb9;
+/// Backlight control 3
+pub const BACKLIGHT_CONTROL_3: u8 = 0xba;
+/// Backlight control 4
+pub const BACKLIGHT_CONTROL_4: u8 = 0xbb;
+/// Backlight control 5
+pub const BACKLIGHT_CONTROL_5: u8 = 0xbc;
+/// Backlight control 7
+pub const BACKLIGHT_CONTROL_7: u8 = 0xbe;
+/// Backlight control 8
+pub const BACKLIGHT_CONTROL_8: u8 = 0xbf;
+/// Power control 1
+pub const POWER_CONTROL_1: u8 = 0xc0;
+/// Power control 2
+pub const POWER_CONTROL_2: u8 = 0xc1;
+/// VCOM control 1
+pub const VCOM_CONTROL_1: u8 = 0xc5;
+/// VCOM control 2
+pub const VCOM_CONTROL_2: u8 = 0xc7;
+/// NV memory write
+pub const NV_MEMORY_WRITE: u8 = 0xd0;
+/// NV memory protection key
+pub const NV_MEMORY_PROTECTION_KEY: u8 = 0xd1;
+/// NV memory status read
+pub const NV_MEMORY_STATUS_READ: u8 = 0xd2;
+/// Read ID4
+pub const READ_ID4: u8 = 0xd3;
+/// Positive gamma correction
+pub const POSITIVE_GAMMA_CORRECTION: u8 = 0xe0;
+/// Negative gamma correction
+pub const NEGATIVE_GAMMA_CORRECTION: u8 = 0xe1;
+/// Digital gamma control 1
+pub const DIGITAL_GAMMA_CONTROL_1: u8 = 0xe2;
+/// Digital gamma control 2
+pub const DIGITAL_GAMMA_CONTROL_2: u8 = 0xe3;
+/// Interface control
+pub const INTERFACE_CONTROL: u8 = 0xf6;
+
+// Extended register commands
+
+/// Power control A
+pub const POWER_CONTROL_A: u8 = 0xcb;
+/// Power control B
+pub const POWER_CONTROL_B: u8 = 0xcf;
+/// Driver timing control A
+pub const DRIVER_TIMING_CONTROL_A: u8 = 0xe8;
+/// Driver timing control B
+pub const DRIVER_TIMING_CONTROL_B: u8 = 0xea;
+/// Power on sequence control
+pub const POWER_ON_SEQUENCE_CONTROL: u8 = 0xed;
+/// Enable 3 gamma control
+pub const ENABLE_3G: u8 = 0xf2;
+/// Pump ratio control
+pub const PUMP_RATIO_CONTROL: u8 = 0xf7;
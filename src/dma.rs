@@ -0,0 +1,167 @@
+use core::future::Future;
+use core::marker::PhantomData;
+use core::pin::Pin;
+use core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+use crate::{Command, DisplayError, Ili9341, PixelFormat, Result};
+use display_interface::{DataFormat, WriteOnlyDataCommand};
+
+/// Interface that can send pixel data in the background, e.g. with DMA
+pub trait DmaCapableInterface: WriteOnlyDataCommand {
+    /// Start sending `data` as pixel data, without waiting for completion
+    ///
+    /// # Safety
+    ///
+    /// The caller guarantees that `data` is not modified or freed until
+    /// [DmaCapableInterface::poll_transfer] returns [Poll::Ready].
+    unsafe fn start_transfer(&mut self, data: &[u16]) -> Result;
+
+    /// Check whether the last transfer is complete
+    ///
+    /// If it is still running, `cx`'s waker must be woken once it completes,
+    /// usually from the DMA interrupt handler.
+    fn poll_transfer(&mut self, cx: &mut Context<'_>) -> Poll<Result>;
+}
+
+/// Waker that does nothing, for busy-waiting on a transfer
+pub(crate) fn noop_waker() -> Waker {
+    fn clone(_: *const ()) -> RawWaker {
+        RawWaker::new(core::ptr::null(), &VTABLE)
+    }
+    fn noop(_: *const ()) {}
+    static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+    // SAFETY: the vtable functions ignore the data pointer and do nothing
+    unsafe { Waker::from_raw(clone(core::ptr::null())) }
+}
+
+/// A pixel transfer running in the background, returned by
+/// [Ili9341::draw_raw_slice_dma]
+///
+/// It can be `.await`ed in async code or waited on with [DmaHandle::wait].
+/// Dropping the handle blocks until the transfer is complete, as the buffer
+/// must not be released before that. Forgetting it instead, e.g. with
+/// [core::mem::forget], leaves the transfer reading the buffer after its
+/// borrow ends, which is why [Ili9341::draw_raw_slice_dma] is unsafe.
+#[must_use]
+pub struct DmaHandle<'a, 'buf, IFACE: DmaCapableInterface> {
+    interface: &'a mut IFACE,
+    done: bool,
+    _buf: PhantomData<&'buf [u16]>,
+}
+
+impl<IFACE: DmaCapableInterface> DmaHandle<'_, '_, IFACE> {
+    /// Block until the transfer is complete
+    pub fn wait(mut self) -> Result {
+        self.block()
+    }
+
+    fn block(&mut self) -> Result {
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            if let Poll::Ready(result) = self.poll_once(&mut cx) {
+                return result;
+            }
+        }
+    }
+
+    fn poll_once(&mut self, cx: &mut Context<'_>) -> Poll<Result> {
+        if self.done {
+            return Poll::Ready(Ok(()));
+        }
+        let poll = self.interface.poll_transfer(cx);
+        self.done = poll.is_ready();
+        poll
+    }
+}
+
+impl<IFACE: DmaCapableInterface> Future for DmaHandle<'_, '_, IFACE> {
+    type Output = Result;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result> {
+        self.get_mut().poll_once(cx)
+    }
+}
+
+impl<IFACE: DmaCapableInterface> Drop for DmaHandle<'_, '_, IFACE> {
+    fn drop(&mut self) {
+        let _ = self.block();
+    }
+}
+
+impl<IFACE, RESET> Ili9341<IFACE, RESET>
+where
+    IFACE: WriteOnlyDataCommand,
+{
+    /// Set the window to the rectangle represented by top-left corner
+    /// (x0, y0) and bottom-right corner (x1, y1) and start a memory write,
+    /// without sending any pixel
+    ///
+    /// This splits a draw in steps for interfaces without
+    /// [DmaCapableInterface]: after this call, the pixel bytes can be sent
+    /// with [Ili9341::dma_write_bytes], or by a DMA transfer started by the
+    /// caller on the underlying bus, then [Ili9341::dma_end_frame] closes the
+    /// frame. No other command may be sent in between.
+    pub fn dma_begin_frame(&mut self, x0: u16, y0: u16, x1: u16, y1: u16) -> Result {
+        self.set_window(x0, y0, x1, y1)?;
+        self.command(Command::MemoryWrite, &[])
+    }
+
+    /// Send raw pixel bytes after [Ili9341::dma_begin_frame], unchanged, in
+    /// the current [PixelFormat]
+    pub fn dma_write_bytes(&mut self, data: &[u8]) -> Result {
+        self.interface.send_data(DataFormat::U8(data))
+    }
+
+    /// Close a frame started with [Ili9341::dma_begin_frame]
+    ///
+    /// Nothing needs to be sent to the display, this currently does nothing
+    /// but marks where a DMA transfer must be complete.
+    pub fn dma_end_frame(&mut self) -> Result {
+        Ok(())
+    }
+}
+
+impl<IFACE, RESET> Ili9341<IFACE, RESET>
+where
+    IFACE: DmaCapableInterface,
+{
+    /// Start drawing a rectangle, represented by top-left corner (x0, y0)
+    /// and bottom-right corner (x1, y1), in the background
+    ///
+    /// The window is set up synchronously, then the pixels of `buf` are sent
+    /// by the interface while the returned [DmaHandle] keeps both the display
+    /// and the buffer borrowed.
+    ///
+    /// Pixels are sent unchanged, so [DisplayError::InvalidFormatError] is
+    /// returned unless the display uses [PixelFormat::Bits16].
+    ///
+    /// # Safety
+    ///
+    /// The returned [DmaHandle] must be awaited, waited on or dropped; it
+    /// must not be leaked, e.g. with [core::mem::forget]. Otherwise `buf` can
+    /// be freed or modified while the transfer is still reading it.
+    pub unsafe fn draw_raw_slice_dma<'a, 'buf>(
+        &'a mut self,
+        x0: u16,
+        y0: u16,
+        x1: u16,
+        y1: u16,
+        buf: &'buf [u16],
+    ) -> Result<DmaHandle<'a, 'buf, IFACE>> {
+        if self.pixel_format() != PixelFormat::Bits16 {
+            return Err(DisplayError::InvalidFormatError);
+        }
+        self.set_window(x0, y0, x1, y1)?;
+        self.command(Command::MemoryWrite, &[])?;
+        // SAFETY: the handle borrows `buf` and waits for the transfer to
+        // complete before releasing it, even when dropped, and the caller
+        // guarantees it is not leaked
+        unsafe { self.interface.start_transfer(buf)? };
+        Ok(DmaHandle {
+            interface: &mut self.interface,
+            done: false,
+            _buf: PhantomData,
+        })
+    }
+}
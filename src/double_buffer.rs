@@ -0,0 +1,303 @@
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::InputPin;
+
+use crate::{Command, DisplayError, Ili9341, Result, Scroller};
+use display_interface::WriteOnlyDataCommand;
+
+/// Time for the display to finish the frame it is refreshing, at frame rates
+/// down to 50Hz
+const FRAME_PERIOD_MS: u32 = 20;
+
+/// Number of reads of the tearing effect pin after which waiting for an edge
+/// gives up, well over a frame on any MCU fast enough to drive the display
+const TE_WAIT_MAX_POLLS: u32 = 1_000_000;
+
+/// MADCTL row address order bit, which flips the display memory vertically
+const MADCTL_MY: u8 = 0x80;
+
+/// Wait for the rising edge of the tearing effect pin, at the beginning of
+/// the vertical blanking period
+///
+/// If the pin is already high, the current blanking period is let go by
+/// first. Returns [DisplayError::BusWriteError] if `te` can't be read or
+/// doesn't change within [TE_WAIT_MAX_POLLS] reads.
+fn wait_vsync<TE: InputPin>(te: &mut TE) -> Result {
+    let mut polls = 0;
+    let mut wait_while = |high: bool| {
+        while te.is_high().map_err(|_| DisplayError::BusWriteError)? == high {
+            polls += 1;
+            if polls > TE_WAIT_MAX_POLLS {
+                return Err(DisplayError::BusWriteError);
+            }
+        }
+        Ok(())
+    };
+    wait_while(true)?;
+    wait_while(false)
+}
+
+/// Two frames sharing the display memory, set up by
+/// [Ili9341::configure_double_buffer]
+///
+/// The scroll area is split in two halves, one is shown while the other is
+/// drawn, and [Ili9341::swap_buffers] switches them by changing the scroll
+/// offset, without copying any pixel.
+pub struct DoubleBuffer {
+    active_frame: u8,
+    frame_lines: u16,
+}
+
+impl DoubleBuffer {
+    /// Index, 0 or 1, of the frame currently shown
+    pub fn active_frame(&self) -> u8 {
+        self.active_frame
+    }
+
+    /// First line of the frame that is not shown, where the next frame must
+    /// be drawn
+    ///
+    /// This is a `y` coordinate for the drawing methods, which match the
+    /// lines of the display memory as double buffering requires a portrait
+    /// orientation that isn't flipped vertically.
+    pub fn back_buffer_y(&self) -> u16 {
+        (1 - u16::from(self.active_frame)) * self.frame_lines
+    }
+
+    /// Number of lines of each frame
+    pub fn frame_lines(&self) -> u16 {
+        self.frame_lines
+    }
+}
+
+impl<IFACE, RESET> Ili9341<IFACE, RESET>
+where
+    IFACE: WriteOnlyDataCommand,
+{
+    /// Split the display memory in two frames of half the height and show
+    /// the first one
+    ///
+    /// Partial mode is enabled so that only the top half of the panel is
+    /// lit, the other half shows the frame being drawn and stays black.
+    /// The display must be in a portrait orientation, where the frames are
+    /// the top and bottom halves of the screen. In landscape, scrolling moves
+    /// the memory horizontally and [DoubleBuffer::back_buffer_y] would not be
+    /// a `y` coordinate. The orientation must not flip the rows either, as in
+    /// [Orientation::PortraitFlipped](crate::Orientation::PortraitFlipped):
+    /// the `y` coordinates then run from the bottom of the display memory,
+    /// while scrolling and partial mode still count lines from its top.
+    /// `scroller` must cover the whole display, without fixed lines.
+    /// Otherwise [DisplayError::InvalidFormatError] is returned. `scroller`
+    /// must not be used to scroll while double buffering.
+    pub fn configure_double_buffer(&mut self, scroller: &mut Scroller) -> Result<DoubleBuffer> {
+        if self.is_landscape()
+            || self.madctl & MADCTL_MY != 0
+            || scroller.fixed_top_lines != 0
+            || scroller.fixed_bottom_lines != 0
+        {
+            return Err(DisplayError::InvalidFormatError);
+        }
+        let frame_lines = scroller.height / 2;
+        if frame_lines == 0 {
+            return Err(DisplayError::InvalidFormatError);
+        }
+
+        let [end_hi, end_lo] = (frame_lines - 1).to_be_bytes();
+        self.command(Command::PartialArea, &[0, 0, end_hi, end_lo])?;
+        self.command(Command::PartialModeOn, &[])?;
+        self.scroll_to_line(scroller, 0)?;
+        Ok(DoubleBuffer {
+            active_frame: 0,
+            frame_lines,
+        })
+    }
+
+    /// Show the frame that was drawn in the back buffer
+    ///
+    /// The new scroll offset only takes effect with the next refresh, so this
+    /// waits for the frame in progress to complete before returning, to make
+    /// sure the old frame isn't drawn over while it is still shown. With
+    /// `vsync`, the tearing effect pin, this waits for the next vertical
+    /// blanking period, and the tearing effect output must be enabled.
+    /// Without it, there is no way to tell where the refresh is, so this
+    /// waits for a whole frame period with `delay` instead.
+    ///
+    /// Returns [DisplayError::BusWriteError] if `vsync` can't be read or
+    /// never rises.
+    pub fn swap_buffers<TE: InputPin, DELAY: DelayNs>(
+        &mut self,
+        buf: &mut DoubleBuffer,
+        vsync: Option<&mut TE>,
+        delay: &mut DELAY,
+    ) -> Result {
+        let back = buf.back_buffer_y();
+        self.write_scroll_offset(back)?;
+        buf.active_frame = 1 - buf.active_frame;
+        match vsync {
+            Some(te) => wait_vsync(te),
+            None => {
+                delay.delay_ms(FRAME_PERIOD_MS);
+                Ok(())
+            }
+        }
+    }
+
+    /// Leave partial mode and go back to showing the whole display memory,
+    /// without scrolling
+    pub fn end_double_buffer(&mut self, buf: DoubleBuffer) -> Result {
+        let _ = buf;
+        self.command(Command::NormalDisplayModeOn, &[])?;
+        self.write_scroll_offset(0)
+    }
+
+    /// Write a frame of full width rows starting at line `first_line`, then
+    /// optionally scroll to it
+    ///
+    /// With `vsync`, the tearing effect pin, the write starts on its rising
+    /// edge, at the beginning of the vertical blanking period, and the
+    /// tearing effect output must be enabled. With `scroller`, the scroll
+    /// offset is moved to `first_line` once the frame is written, which
+    /// makes a frame drawn out of view appear at once.
+    ///
+    /// Returns [DisplayError::InvalidFormatError] if `frame` is not a whole
+    /// number of rows, [DisplayError::OutOfBoundsError] if it goes past the
+    /// bottom of the display or `first_line` is outside the scroll area of
+    /// `scroller`, and [DisplayError::BusWriteError] if `vsync` can't be read
+    /// or never rises. Nothing is written when the arguments are rejected.
+    pub fn commit_frame<TE: InputPin>(
+        &mut self,
+        frame: &[u16],
+        first_line: u16,
+        scroller: Option<&mut Scroller>,
+        vsync: Option<&mut TE>,
+    ) -> Result {
+        let width = self.width();
+        if width == 0 || frame.is_empty() || frame.len() % width != 0 {
+            return Err(DisplayError::InvalidFormatError);
+        }
+        let last_line = (frame.len() / width - 1)
+            .checked_add(usize::from(first_line))
+            .filter(|&line| line < self.height())
+            .ok_or(DisplayError::OutOfBoundsError)? as u16;
+        if let Some(scroller) = &scroller {
+            scroller.check_line(first_line)?;
+        }
+
+        if let Some(te) = vsync {
+            wait_vsync(te)?;
+        }
+        self.draw_raw_slice(0, first_line, width as u16 - 1, last_line, frame)?;
+
+        if let Some(scroller) = scroller {
+            self.scroll_to_line(scroller, first_line)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use crate::mock::{display, take_sent, MockDelay, MockTePin};
+    use crate::Orientation;
+    use std::vec;
+
+    #[test]
+    fn double_buffer_portrait() {
+        let mut display = display();
+        let mut scroller = display.configure_vertical_scroll(0, 0).unwrap();
+        take_sent(&mut display);
+        let buf = display.configure_double_buffer(&mut scroller).unwrap();
+        assert_eq!(buf.back_buffer_y(), 160);
+        assert_eq!(
+            take_sent(&mut display),
+            [
+                (0x30, vec![0, 0, 0, 159]),
+                (0x12, vec![]),
+                (0x37, vec![0, 0]),
+            ]
+        );
+    }
+
+    #[test]
+    fn double_buffer_rejects_landscape() {
+        let mut display = display();
+        display.set_orientation(Orientation::Landscape).unwrap();
+        let mut scroller = display.configure_vertical_scroll(0, 0).unwrap();
+        take_sent(&mut display);
+        assert!(display.configure_double_buffer(&mut scroller).is_err());
+        assert_eq!(take_sent(&mut display), []);
+    }
+
+    #[test]
+    fn double_buffer_rejects_flipped() {
+        let mut display = display();
+        display
+            .set_orientation(Orientation::PortraitFlipped)
+            .unwrap();
+        let mut scroller = display.configure_vertical_scroll(0, 0).unwrap();
+        take_sent(&mut display);
+        assert!(display.configure_double_buffer(&mut scroller).is_err());
+        assert_eq!(take_sent(&mut display), []);
+    }
+
+    #[test]
+    fn swap_buffers_waits_for_vsync() {
+        let mut display = display();
+        let mut scroller = display.configure_vertical_scroll(0, 0).unwrap();
+        let mut buf = display.configure_double_buffer(&mut scroller).unwrap();
+        take_sent(&mut display);
+        // Called during a blanking period, which is let go by before waiting
+        // for the next one
+        let mut te = MockTePin::new(&[true, false, false, true]);
+        display
+            .swap_buffers(&mut buf, Some(&mut te), &mut MockDelay)
+            .unwrap();
+        assert_eq!(te.reads, 4);
+        assert_eq!(buf.active_frame(), 1);
+        assert_eq!(take_sent(&mut display), [(0x37, vec![0, 160])]);
+    }
+
+    #[test]
+    fn swap_buffers_vsync_timeout() {
+        let mut display = display();
+        let mut scroller = display.configure_vertical_scroll(0, 0).unwrap();
+        let mut buf = display.configure_double_buffer(&mut scroller).unwrap();
+        let mut te = MockTePin::new(&[false]);
+        assert!(display
+            .swap_buffers(&mut buf, Some(&mut te), &mut MockDelay)
+            .is_err());
+    }
+
+    #[test]
+    fn commit_frame_rejects_line_outside_scroll_area() {
+        let mut display = display();
+        let mut scroller = display.configure_vertical_scroll(10, 10).unwrap();
+        take_sent(&mut display);
+        let frame = vec![0; 240];
+        let mut te = MockTePin::new(&[false, true]);
+        assert!(display
+            .commit_frame(&frame, 315, Some(&mut scroller), Some(&mut te))
+            .is_err());
+        assert_eq!(te.reads, 0);
+        assert_eq!(take_sent(&mut display), []);
+    }
+
+    #[test]
+    fn commit_frame_waits_for_vsync() {
+        let mut display = display();
+        let frame = vec![0; 240];
+        let mut te = MockTePin::new(&[false, true]);
+        display
+            .commit_frame(&frame, 0, None, Some(&mut te))
+            .unwrap();
+        assert_eq!(te.reads, 2);
+        assert_eq!(take_sent(&mut display)[0].0, 0x2a);
+
+        let mut te = MockTePin::new(&[true]);
+        assert!(display
+            .commit_frame(&frame, 0, None, Some(&mut te))
+            .is_err());
+        assert_eq!(take_sent(&mut display), []);
+    }
+}
@@ -0,0 +1,198 @@
+use core::convert::TryFrom;
+
+use crate::color::rgb888_to_rgb565;
+use crate::{window_size, DisplayError, Ili9341, PixelFormat, Result};
+use display_interface::WriteOnlyDataCommand;
+
+impl<IFACE, RESET> Ili9341<IFACE, RESET>
+where
+    IFACE: WriteOnlyDataCommand,
+{
+    /// Fill a rectangle, represented by top-left corner (x0, y0) and
+    /// bottom-right corner (x1, y1), by repeating a tile of rgb565 pixels
+    ///
+    /// `tile` holds `tile_width * tile_height` pixels, row by row. The tile
+    /// is aligned with the top-left corner of the rectangle.
+    #[allow(clippy::too_many_arguments)]
+    pub fn fill_with_pattern(
+        &mut self,
+        x0: u16,
+        y0: u16,
+        x1: u16,
+        y1: u16,
+        tile: &[u16],
+        tile_width: u16,
+        tile_height: u16,
+    ) -> Result {
+        window_size(x0, y0, x1, y1)?;
+        let tile_width = usize::from(tile_width);
+        let tile_height = usize::from(tile_height);
+        if tile_width == 0 || tile_height == 0 || tile.len() < tile_width * tile_height {
+            return Err(DisplayError::InvalidFormatError);
+        }
+
+        let width = usize::from(x1 - x0) + 1;
+        let tile_rows = tile.chunks_exact(tile_width).take(tile_height).cycle();
+        let pixels = (y0..=y1)
+            .zip(tile_rows)
+            .flat_map(|(_, row)| row.iter().copied().cycle().take(width));
+        self.draw_raw_iter(x0, y0, x1, y1, pixels)
+    }
+
+    /// Fill a rectangle, represented by top-left corner (x0, y0) and
+    /// bottom-right corner (x1, y1), with `color` in chunks of about
+    /// `chunk_pixels` pixels, calling `watchdog_feed` after every chunk
+    ///
+    /// Large fills can take longer than a short watchdog period. Chunks are
+    /// made of whole rows, or of parts of a row when `chunk_pixels` is smaller
+    /// than the width; smaller chunks feed the watchdog more often at the cost
+    /// of more transactions.
+    #[allow(clippy::too_many_arguments)]
+    pub fn chunked_draw<F: FnMut()>(
+        &mut self,
+        x0: u16,
+        y0: u16,
+        x1: u16,
+        y1: u16,
+        color: u16,
+        chunk_pixels: u32,
+        mut watchdog_feed: F,
+    ) -> Result {
+        window_size(x0, y0, x1, y1)?;
+        let chunk_pixels = chunk_pixels.max(1);
+        let width = u32::from(x1 - x0) + 1;
+
+        if chunk_pixels >= width {
+            let rows = (chunk_pixels / width).min(u32::from(u16::MAX)) as u16;
+            let mut y = y0;
+            loop {
+                let chunk_y1 = y.saturating_add(rows - 1).min(y1);
+                let pixels = window_size(x0, y, x1, chunk_y1)?;
+                self.draw_raw_iter(x0, y, x1, chunk_y1, core::iter::repeat(color).take(pixels))?;
+                watchdog_feed();
+                if chunk_y1 == y1 {
+                    return Ok(());
+                }
+                y = chunk_y1 + 1;
+            }
+        }
+
+        let segment = chunk_pixels as u16;
+        for y in y0..=y1 {
+            let mut x = x0;
+            loop {
+                let segment_x1 = x.saturating_add(segment - 1).min(x1);
+                let pixels = usize::from(segment_x1 - x) + 1;
+                self.draw_raw_iter(x, y, segment_x1, y, core::iter::repeat(color).take(pixels))?;
+                watchdog_feed();
+                if segment_x1 == x1 {
+                    break;
+                }
+                x = segment_x1 + 1;
+            }
+        }
+        Ok(())
+    }
+
+    /// Draw an image of big endian rgb565 pixels, `width` pixels wide, with
+    /// its top-left corner at (x, y)
+    ///
+    /// This is the layout of the data an
+    /// `embedded_graphics::image::ImageRaw<Rgb565, BigEndian>` is created
+    /// from, so the same slice can be drawn directly.
+    /// With [PixelFormat::Bits16] the bytes are sent as they are, without
+    /// extracting each pixel like drawing through [DrawTarget] does.
+    ///
+    /// Returns [DisplayError::InvalidFormatError] if `data` doesn't hold a
+    /// whole number of rows.
+    ///
+    /// [DrawTarget]: https://docs.rs/embedded-graphics-core/latest/embedded_graphics_core/draw_target/trait.DrawTarget.html
+    pub fn draw_image_raw(&mut self, x: u16, y: u16, width: u16, data: &[u8]) -> Result {
+        let row_bytes = usize::from(width) * 2;
+        if row_bytes == 0 || data.is_empty() || data.len() % row_bytes != 0 {
+            return Err(DisplayError::InvalidFormatError);
+        }
+        let height = data.len() / row_bytes;
+        let x1 = x
+            .checked_add(width - 1)
+            .ok_or(DisplayError::OutOfBoundsError)?;
+        let y1 = u16::try_from(height - 1)
+            .ok()
+            .and_then(|h| y.checked_add(h))
+            .ok_or(DisplayError::OutOfBoundsError)?;
+
+        match self.pixel_format {
+            PixelFormat::Bits16 => self.draw_raw_bytes(x, y, x1, y1, data),
+            _ => {
+                let pixels = data
+                    .chunks_exact(2)
+                    .map(|bytes| u16::from_be_bytes([bytes[0], bytes[1]]));
+                self.draw_raw_iter(x, y, x1, y1, pixels)
+            }
+        }
+    }
+
+    /// Fill a `width` x `height` rectangle with its top-left corner at
+    /// (x0, y0), choosing `fg` or `bg` for each pixel with a 1 bit mask
+    ///
+    /// Each row of `mask` is `width` bits padded to a whole byte, most
+    /// significant bit first; a set bit selects `fg`. Returns
+    /// [DisplayError::InvalidFormatError] if `mask` is too short.
+    #[allow(clippy::too_many_arguments)]
+    pub fn bitmask_fill(
+        &mut self,
+        x0: u16,
+        y0: u16,
+        width: u16,
+        height: u16,
+        mask: &[u8],
+        fg: u16,
+        bg: u16,
+    ) -> Result {
+        if width == 0 || height == 0 {
+            return Ok(());
+        }
+        let row_bytes = usize::from(width).div_ceil(8);
+        if mask.len() < row_bytes * usize::from(height) {
+            return Err(DisplayError::InvalidFormatError);
+        }
+        let x1 = x0
+            .checked_add(width - 1)
+            .ok_or(DisplayError::OutOfBoundsError)?;
+        let y1 = y0
+            .checked_add(height - 1)
+            .ok_or(DisplayError::OutOfBoundsError)?;
+
+        let pixels = mask
+            .chunks_exact(row_bytes)
+            .take(usize::from(height))
+            .flat_map(|row| {
+                (0..usize::from(width)).map(move |col| {
+                    if row[col / 8] & (0x80 >> (col % 8)) != 0 {
+                        fg
+                    } else {
+                        bg
+                    }
+                })
+            });
+        self.draw_raw_iter(x0, y0, x1, y1, pixels)
+    }
+
+    /// Same as [Ili9341::draw_raw_iter], with 24 bit `(r, g, b)` pixels that
+    /// are converted to rgb565 as they are sent
+    ///
+    /// This avoids a conversion buffer when drawing decoded JPEG or BMP data.
+    pub fn draw_raw_rgb888_iter<I: IntoIterator<Item = (u8, u8, u8)>>(
+        &mut self,
+        x0: u16,
+        y0: u16,
+        x1: u16,
+        y1: u16,
+        pixels: I,
+    ) -> Result {
+        let pixels = pixels
+            .into_iter()
+            .map(|(r, g, b)| rgb888_to_rgb565(r, g, b));
+        self.draw_raw_iter(x0, y0, x1, y1, pixels)
+    }
+}
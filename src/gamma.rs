@@ -0,0 +1,53 @@
+use crate::{Command, Ili9341, Result};
+use display_interface::WriteOnlyDataCommand;
+
+/// Gamma curves built into the display, selected with
+/// [Ili9341::set_gamma_curve]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GammaCurve {
+    /// Gamma 2.2, the default
+    Curve1 = 0x01,
+    /// Gamma 1.8
+    Curve2 = 0x02,
+    /// Gamma 2.5
+    Curve3 = 0x04,
+    /// Gamma 1.0
+    Curve4 = 0x08,
+}
+
+/// Positive gamma correction table used by most ILI9341 modules, e.g. in the
+/// Adafruit and M5Stack initialization sequences
+pub const POSITIVE_GAMMA_COMMON: [u8; 15] = [
+    0x0f, 0x31, 0x2b, 0x0c, 0x0e, 0x08, 0x4e, 0xf1, 0x37, 0x07, 0x10, 0x03, 0x0e, 0x09, 0x00,
+];
+
+/// Negative gamma correction table matching [POSITIVE_GAMMA_COMMON]
+pub const NEGATIVE_GAMMA_COMMON: [u8; 15] = [
+    0x00, 0x0e, 0x14, 0x03, 0x11, 0x07, 0x31, 0xc1, 0x48, 0x08, 0x0f, 0x0c, 0x31, 0x36, 0x0f,
+];
+
+impl<IFACE, RESET> Ili9341<IFACE, RESET>
+where
+    IFACE: WriteOnlyDataCommand,
+{
+    /// Select one of the gamma curves built into the display (26h)
+    pub fn set_gamma_curve(&mut self, curve: GammaCurve) -> Result {
+        self.command(Command::GammaSet, &[curve as _])
+    }
+
+    /// Upload the positive gamma correction table (E0h), e.g.
+    /// [POSITIVE_GAMMA_COMMON]
+    ///
+    /// The panel vendor usually provides the table, as it depends on the
+    /// liquid crystal. It overrides the curve set with
+    /// [Ili9341::set_gamma_curve].
+    pub fn set_positive_gamma_correction(&mut self, table: &[u8; 15]) -> Result {
+        self.command(Command::PositiveGammaCorrection, table)
+    }
+
+    /// Upload the negative gamma correction table (E1h), e.g.
+    /// [NEGATIVE_GAMMA_COMMON]
+    pub fn set_negative_gamma_correction(&mut self, table: &[u8; 15]) -> Result {
+        self.command(Command::NegativeGammaCorrection, table)
+    }
+}
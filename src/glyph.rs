@@ -0,0 +1,513 @@
+use core::fmt::Write;
+
+use crate::color::blend_rgb565;
+use crate::small_string::SmallString;
+use crate::{DisplayError, Ili9341, Result, Scroller};
+use display_interface::WriteOnlyDataCommand;
+
+/// Pre-rendered glyphs of a fixed width font, stored as rgb565 pixels
+///
+/// `glyphs` holds the glyphs of consecutive characters starting at
+/// `char_start`, each being `glyph_width * glyph_height` pixels row by row.
+/// Since colors are baked in, such an atlas is usually generated offline and
+/// placed in flash.
+pub struct GlyphAtlas<'a> {
+    glyphs: &'a [u16],
+    glyph_width: u16,
+    glyph_height: u16,
+    char_start: u8,
+}
+
+impl<'a> GlyphAtlas<'a> {
+    pub const fn new(
+        glyphs: &'a [u16],
+        glyph_width: u16,
+        glyph_height: u16,
+        char_start: u8,
+    ) -> Self {
+        GlyphAtlas {
+            glyphs,
+            glyph_width,
+            glyph_height,
+            char_start,
+        }
+    }
+
+    fn glyph_len(&self) -> usize {
+        usize::from(self.glyph_width) * usize::from(self.glyph_height)
+    }
+
+    fn glyph(&self, c: char) -> Option<&'a [u16]> {
+        let index = (c as u32).checked_sub(u32::from(self.char_start))? as usize;
+        let start = index.checked_mul(self.glyph_len())?;
+        self.glyphs.get(start..start + self.glyph_len())
+    }
+}
+
+/// Glyphs of a BDF font rasterized to rgb565, returned by [rasterize_font]
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RasterizedFont {
+    pub glyphs: std::vec::Vec<u16>,
+    pub glyph_width: u16,
+    pub glyph_height: u16,
+    pub char_start: u8,
+}
+
+#[cfg(feature = "std")]
+impl RasterizedFont {
+    /// Atlas drawing from these glyphs
+    pub fn atlas(&self) -> GlyphAtlas<'_> {
+        GlyphAtlas::new(
+            &self.glyphs,
+            self.glyph_width,
+            self.glyph_height,
+            self.char_start,
+        )
+    }
+}
+
+/// Rasterize the characters `chars` of the BDF font `bdf` into an atlas,
+/// drawing set bits with `fg` and the rest with `bg`
+///
+/// Meant for build scripts, which can write [RasterizedFont::glyphs] to a
+/// file included by the firmware:
+///
+/// ```ignore
+/// let font = rasterize_font(&fs::read_to_string("6x13.bdf")?, b' '..=b'~', 0xffff, 0x0000)?;
+/// let out = Path::new(&env::var("OUT_DIR")?).join("font.rs");
+/// fs::write(out, format!("const GLYPHS: [u16; {}] = {:?};", font.glyphs.len(), font.glyphs))?;
+/// ```
+///
+/// Every glyph is placed in a cell of the size of FONTBOUNDINGBOX, aligned
+/// on the baseline. Characters missing from the font are filled with `bg`.
+/// Returns [DisplayError::InvalidFormatError] if the font can't be parsed.
+#[cfg(feature = "std")]
+pub fn rasterize_font(
+    bdf: &str,
+    chars: core::ops::RangeInclusive<u8>,
+    fg: u16,
+    bg: u16,
+) -> Result<RasterizedFont> {
+    use core::convert::TryFrom;
+
+    fn numbers<const N: usize>(args: &str) -> Result<[i32; N]> {
+        let mut values = [0; N];
+        let mut args = args.split_whitespace();
+        for value in values.iter_mut() {
+            *value = args
+                .next()
+                .and_then(|arg| arg.parse().ok())
+                .ok_or(DisplayError::InvalidFormatError)?;
+        }
+        Ok(values)
+    }
+
+    let mut lines = bdf.lines().map(str::trim);
+    let [cell_w, cell_h, cell_x, cell_y] = lines
+        .by_ref()
+        .find_map(|line| line.strip_prefix("FONTBOUNDINGBOX "))
+        .ok_or(DisplayError::InvalidFormatError)
+        .and_then(numbers::<4>)?;
+    let glyph_width = u16::try_from(cell_w).map_err(|_| DisplayError::InvalidFormatError)?;
+    let glyph_height = u16::try_from(cell_h).map_err(|_| DisplayError::InvalidFormatError)?;
+    let glyph_len = usize::from(glyph_width) * usize::from(glyph_height);
+
+    let (first, last) = (*chars.start(), *chars.end());
+    let count = usize::from(last.saturating_sub(first)) + usize::from(first <= last);
+    let mut glyphs = std::vec![bg; count * glyph_len];
+
+    let mut encoding = None;
+    let mut bbx = [0; 4];
+    while let Some(line) = lines.next() {
+        if let Some(args) = line.strip_prefix("ENCODING ") {
+            encoding = Some(numbers::<1>(args)?[0]);
+        } else if let Some(args) = line.strip_prefix("BBX ") {
+            bbx = numbers::<4>(args)?;
+        } else if line == "BITMAP" {
+            let [w, h, x, y] = bbx;
+            let index = encoding
+                .and_then(|c| u8::try_from(c).ok())
+                .filter(|c| chars.contains(c))
+                .map(|c| usize::from(c - first));
+            // Top row of the glyph, counted from the top of the cell
+            let top = cell_y + cell_h - (y + h);
+            for row in 0..h {
+                let bits = lines.next().ok_or(DisplayError::InvalidFormatError)?;
+                let glyph = match index {
+                    Some(index) => &mut glyphs[index * glyph_len..][..glyph_len],
+                    None => continue,
+                };
+                let cell_row = top + row;
+                if !(0..cell_h).contains(&cell_row) {
+                    continue;
+                }
+                for column in 0..w {
+                    let byte = bits
+                        .get(2 * (column / 8) as usize..)
+                        .and_then(|hex| hex.get(..2))
+                        .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                        .ok_or(DisplayError::InvalidFormatError)?;
+                    let cell_column = x - cell_x + column;
+                    if byte & (0x80 >> (column % 8)) != 0 && (0..cell_w).contains(&cell_column) {
+                        glyph[(cell_row * cell_w + cell_column) as usize] = fg;
+                    }
+                }
+            }
+            encoding = None;
+        }
+    }
+
+    Ok(RasterizedFont {
+        glyphs,
+        glyph_width,
+        glyph_height,
+        char_start: first,
+    })
+}
+
+/// Glyphs of a fixed width font stored as 1 bit per pixel
+///
+/// `glyphs` holds the glyphs of consecutive characters starting at
+/// `char_start`. Each glyph is `glyph_height` rows, and each row is
+/// `glyph_width` bits padded to a whole byte, most significant bit first.
+/// A set bit is drawn with the foreground color.
+pub struct BitmapFont<'a> {
+    glyphs: &'a [u8],
+    glyph_width: u16,
+    glyph_height: u16,
+    char_start: u8,
+}
+
+impl<'a> BitmapFont<'a> {
+    pub const fn new(
+        glyphs: &'a [u8],
+        glyph_width: u16,
+        glyph_height: u16,
+        char_start: u8,
+    ) -> Self {
+        BitmapFont {
+            glyphs,
+            glyph_width,
+            glyph_height,
+            char_start,
+        }
+    }
+
+    fn row_bytes(&self) -> usize {
+        usize::from(self.glyph_width).div_ceil(8)
+    }
+
+    fn glyph(&self, c: char) -> Option<&'a [u8]> {
+        let glyph_len = self.row_bytes() * usize::from(self.glyph_height);
+        let index = (c as u32).checked_sub(u32::from(self.char_start))? as usize;
+        let start = index.checked_mul(glyph_len)?;
+        self.glyphs.get(start..start + glyph_len)
+    }
+}
+
+impl<IFACE, RESET> Ili9341<IFACE, RESET>
+where
+    IFACE: WriteOnlyDataCommand,
+{
+    /// Draw a single line of text with its top-left corner at (x, y), using
+    /// one memory write per character
+    ///
+    /// Characters missing from the atlas are drawn as a box filled with `bg`.
+    /// Drawing stops at the first character that doesn't fit on the screen,
+    /// and text that doesn't fit vertically returns
+    /// [DisplayError::OutOfBoundsError].
+    pub fn draw_string_fast(
+        &mut self,
+        x: u16,
+        y: u16,
+        text: &str,
+        atlas: &GlyphAtlas,
+        bg: u16,
+    ) -> Result {
+        let (w, h) = (atlas.glyph_width, atlas.glyph_height);
+        if w == 0 || h == 0 {
+            return Ok(());
+        }
+        if usize::from(y) + usize::from(h) > self.height() {
+            return Err(DisplayError::OutOfBoundsError);
+        }
+        let y1 = y + h - 1;
+
+        let mut x0 = usize::from(x);
+        for c in text.chars() {
+            if x0 + usize::from(w) > self.width() {
+                break;
+            }
+            let x1 = (x0 + usize::from(w) - 1) as u16;
+            match atlas.glyph(c) {
+                Some(glyph) => self.draw_raw_slice(x0 as u16, y, x1, y1, glyph)?,
+                None => {
+                    let fill = core::iter::repeat(bg).take(atlas.glyph_len());
+                    self.draw_raw_iter(x0 as u16, y, x1, y1, fill)?
+                }
+            }
+            x0 += usize::from(w);
+        }
+        Ok(())
+    }
+
+    /// Draw a line of text at the top-left corner of the screen, inside the
+    /// fixed top area of `scroller`, and fill the rest of the line with `bg`
+    ///
+    /// Lines of the fixed area are not moved by scrolling, so the status bar
+    /// stays in place and the scroll position is left untouched. The fixed
+    /// top area must be at least as tall as the glyphs, as configured with
+    /// [Ili9341::configure_vertical_scroll] in a portrait orientation, and
+    /// the text must fit in the width of the display. Otherwise
+    /// [DisplayError::OutOfBoundsError] is returned and nothing is drawn.
+    pub fn draw_status_bar<TEXT: AsRef<str>>(
+        &mut self,
+        text: TEXT,
+        scroller: &Scroller,
+        atlas: &GlyphAtlas,
+        bg: u16,
+    ) -> Result {
+        let (w, h) = (atlas.glyph_width, atlas.glyph_height);
+        let text = text.as_ref();
+        let text_width = text.chars().count() * usize::from(w);
+        // In landscape the fixed area is on the left, not at the top
+        if self.is_landscape() || h > scroller.fixed_top_lines || text_width > self.width() {
+            return Err(DisplayError::OutOfBoundsError);
+        }
+        if w == 0 || h == 0 {
+            return Ok(());
+        }
+        self.draw_string_fast(0, 0, text, atlas, bg)?;
+
+        let right = self.width() as i32 - 1;
+        self.fill_clipped(text_width as i32, 0, right, i32::from(h) - 1, bg)
+    }
+
+    /// Draw a single line of text with its top-left corner at (x, y), with a
+    /// vertical gradient from `top_color` on the first row of the glyphs to
+    /// `bottom_color` on the last one
+    ///
+    /// Characters missing from the font are drawn as a box filled with `bg`.
+    /// Drawing stops at the first character that doesn't fit on the screen,
+    /// and text that doesn't fit vertically returns
+    /// [DisplayError::OutOfBoundsError].
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_gradient_text(
+        &mut self,
+        text: &str,
+        x: u16,
+        y: u16,
+        font: &BitmapFont,
+        top_color: u16,
+        bottom_color: u16,
+        bg: u16,
+    ) -> Result {
+        let (w, h) = (font.glyph_width, font.glyph_height);
+        if w == 0 || h == 0 {
+            return Ok(());
+        }
+        if usize::from(y) + usize::from(h) > self.height() {
+            return Err(DisplayError::OutOfBoundsError);
+        }
+        let y1 = y + h - 1;
+        let row_color = |row: usize| {
+            let t = if h == 1 {
+                0
+            } else {
+                row * 255 / usize::from(h - 1)
+            };
+            blend_rgb565(top_color, bottom_color, t as u8)
+        };
+
+        let mut x0 = usize::from(x);
+        for c in text.chars() {
+            if x0 + usize::from(w) > self.width() {
+                break;
+            }
+            let x1 = (x0 + usize::from(w) - 1) as u16;
+            let glyph = font.glyph(c);
+            let pixels = (0..usize::from(h)).flat_map(|row| {
+                let fg = row_color(row);
+                (0..usize::from(w)).map(move |col| {
+                    let set = glyph.is_some_and(|glyph| {
+                        let byte = glyph[row * font.row_bytes() + col / 8];
+                        byte & (0x80 >> (col % 8)) != 0
+                    });
+                    if set {
+                        fg
+                    } else {
+                        bg
+                    }
+                })
+            });
+            self.draw_raw_iter(x0 as u16, y, x1, y1, pixels)?;
+            x0 += usize::from(w);
+        }
+        Ok(())
+    }
+
+    /// Format `value` and draw it with [Ili9341::draw_string_fast], without
+    /// allocating
+    ///
+    /// Values longer than 16 characters return
+    /// [DisplayError::InvalidFormatError].
+    pub fn draw_number<N: core::fmt::Display>(
+        &mut self,
+        x: u16,
+        y: u16,
+        value: N,
+        atlas: &GlyphAtlas,
+        bg: u16,
+    ) -> Result {
+        let mut text = SmallString::<16>::new();
+        write!(text, "{}", value).map_err(|_| DisplayError::InvalidFormatError)?;
+        self.draw_string_fast(x, y, text.as_str(), atlas, bg)
+    }
+
+    /// Draw the fixed point value `integer / 10^decimals` with `decimals`
+    /// digits after the point, e.g. 235 with 1 decimal as "23.5"
+    ///
+    /// Returns [DisplayError::OutOfBoundsError] if `decimals` is more than 9,
+    /// the most an `i32` can hold.
+    pub fn draw_fixed_point(
+        &mut self,
+        x: u16,
+        y: u16,
+        integer: i32,
+        decimals: u8,
+        atlas: &GlyphAtlas,
+        bg: u16,
+    ) -> Result {
+        if decimals == 0 {
+            return self.draw_number(x, y, integer, atlas, bg);
+        }
+        if decimals > 9 {
+            return Err(DisplayError::OutOfBoundsError);
+        }
+        let divisor = 10u32.pow(u32::from(decimals));
+        let abs = integer.unsigned_abs();
+        let sign = if integer < 0 { "-" } else { "" };
+
+        let mut text = SmallString::<16>::new();
+        write!(
+            text,
+            "{}{}.{:0width$}",
+            sign,
+            abs / divisor,
+            abs % divisor,
+            width = usize::from(decimals)
+        )
+        .map_err(|_| DisplayError::InvalidFormatError)?;
+        self.draw_string_fast(x, y, text.as_str(), atlas, bg)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::GlyphAtlas;
+    use crate::mock::{display, take_sent};
+    use std::vec::Vec;
+
+    /// 1x1 glyphs from '-' to '9', each pixel holding its index
+    const GLYPHS: [u16; 13] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12];
+    const ATLAS: GlyphAtlas = GlyphAtlas::new(&GLYPHS, 1, 1, b'-');
+
+    #[test]
+    fn fixed_point() {
+        let mut display = display();
+        display.draw_fixed_point(0, 0, -235, 1, &ATLAS, 0).unwrap();
+        let pixels: Vec<u8> = take_sent(&mut display)
+            .into_iter()
+            .filter(|(cmd, _)| *cmd == 0x2c)
+            .map(|(_, data)| data[1])
+            .collect();
+        // "-23.5"
+        assert_eq!(pixels, [0, 5, 6, 1, 8]);
+    }
+
+    #[test]
+    fn fixed_point_too_many_decimals() {
+        let mut display = display();
+        assert!(display.draw_fixed_point(0, 0, 1, 9, &ATLAS, 0).is_ok());
+        take_sent(&mut display);
+        assert!(display.draw_fixed_point(0, 0, 1, 10, &ATLAS, 0).is_err());
+        assert_eq!(take_sent(&mut display), []);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn rasterize_bdf() {
+        use super::rasterize_font;
+
+        // 3x4 cell with the baseline one row above the bottom. 'A' is a 2x3
+        // block resting on the baseline, 'B' a 1x1 dot at the bottom right
+        // of the cell, 'C' is missing
+        let bdf = "STARTFONT 2.1
+FONT test
+SIZE 4 75 75
+FONTBOUNDINGBOX 3 4 0 -1
+CHARS 2
+STARTCHAR A
+ENCODING 65
+BBX 2 3 0 0
+BITMAP
+C0
+40
+80
+ENDCHAR
+STARTCHAR B
+ENCODING 66
+BBX 1 1 2 -1
+BITMAP
+80
+ENDCHAR
+ENDFONT
+";
+        let font = rasterize_font(bdf, b'A'..=b'C', 1, 0).unwrap();
+        assert_eq!((font.glyph_width, font.glyph_height), (3, 4));
+        assert_eq!(font.char_start, b'A');
+        #[rustfmt::skip]
+        let expected = [
+            // A
+            1, 1, 0,
+            0, 1, 0,
+            1, 0, 0,
+            0, 0, 0,
+            // B
+            0, 0, 0,
+            0, 0, 0,
+            0, 0, 0,
+            0, 0, 1,
+            // C
+            0, 0, 0,
+            0, 0, 0,
+            0, 0, 0,
+            0, 0, 0,
+        ];
+        assert_eq!(font.glyphs, expected);
+
+        assert!(rasterize_font("STARTFONT 2.1", b'A'..=b'A', 1, 0).is_err());
+    }
+
+    #[test]
+    fn status_bar_too_wide() {
+        let mut display = display();
+        let scroller = display.configure_vertical_scroll(1, 0).unwrap();
+        take_sent(&mut display);
+
+        let fits = "0".repeat(240);
+        display
+            .draw_status_bar(&fits, &scroller, &ATLAS, 0)
+            .unwrap();
+        assert_eq!(take_sent(&mut display).len(), 3 * 240);
+
+        let too_wide = "0".repeat(241);
+        assert!(display
+            .draw_status_bar(&too_wide, &scroller, &ATLAS, 0)
+            .is_err());
+        assert_eq!(take_sent(&mut display), []);
+    }
+}
@@ -1,10 +1,79 @@
+use core::convert::TryFrom;
+
 use crate::Ili9341;
+use display_interface::DisplayError;
 use embedded_graphics_core::{
     pixelcolor::{raw::RawU16, Rgb565},
     prelude::*,
     primitives::Rectangle,
 };
 
+/// Default number of pixels [DrawTarget::draw_iter] groups into one transfer
+pub(crate) const DRAW_ITER_BATCH: usize = 16;
+
+impl<IFACE, RESET> Ili9341<IFACE, RESET>
+where
+    IFACE: display_interface::WriteOnlyDataCommand,
+{
+    /// Draw individual pixels, like [DrawTarget::draw_iter], grouping up to `N`
+    /// horizontally adjacent pixels of the same row into a single transfer
+    ///
+    /// Primitives such as text and lines produce their pixels row by row, so
+    /// this saves setting a new window for every pixel.
+    pub fn draw_iter_batched<const N: usize, I>(&mut self, pixels: I) -> Result<(), DisplayError>
+    where
+        I: IntoIterator<Item = Pixel<Rgb565>>,
+    {
+        let mut row = [0; N];
+        let mut len = 0;
+        let (mut x0, mut y) = (0, 0);
+
+        for Pixel(point, color) in pixels {
+            let (px, py) = match self.to_display_coords(point) {
+                Some(coords) => coords,
+                None => continue,
+            };
+            let color = RawU16::from(color).into_inner();
+            if N == 0 {
+                self.draw_raw_slice(px, py, px, py, &[color])?;
+                continue;
+            }
+
+            let adjacent = len > 0 && py == y && usize::from(px) == usize::from(x0) + len;
+            if !adjacent || len == N {
+                self.flush_row(x0, y, &row[..len])?;
+                len = 0;
+                x0 = px;
+                y = py;
+            }
+            row[len] = color;
+            len += 1;
+        }
+        self.flush_row(x0, y, &row[..len])
+    }
+
+    fn flush_row(&mut self, x0: u16, y: u16, row: &[u16]) -> Result<(), DisplayError> {
+        if row.is_empty() {
+            return Ok(());
+        }
+        self.draw_raw_slice(x0, y, x0 + row.len() as u16 - 1, y, row)
+    }
+}
+
+impl<IFACE, RESET> Ili9341<IFACE, RESET> {
+    /// Convert an embedded-graphics point to display coordinates, or `None`
+    /// if it is outside of the display
+    pub fn to_display_coords(&self, point: Point) -> Option<(u16, u16)> {
+        let x = u16::try_from(point.x).ok()?;
+        let y = u16::try_from(point.y).ok()?;
+        if usize::from(x) < self.width() && usize::from(y) < self.height() {
+            Some((x, y))
+        } else {
+            None
+        }
+    }
+}
+
 impl<IFACE, RESET> OriginDimensions for Ili9341<IFACE, RESET> {
     fn size(&self) -> Size {
         Size::new(self.width() as u32, self.height() as u32)
@@ -23,15 +92,7 @@ where
     where
         I: IntoIterator<Item = Pixel<Self::Color>>,
     {
-        for Pixel(point, color) in pixels {
-            if self.bounding_box().contains(point) {
-                let x = point.x as u16;
-                let y = point.y as u16;
-                let color = RawU16::from(color).into_inner();
-                self.draw_raw_slice(x, y, x, y, &[color])?;
-            }
-        }
-        Ok(())
+        self.draw_iter_batched::<DRAW_ITER_BATCH, _>(pixels)
     }
 
     fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
@@ -58,17 +119,38 @@ where
                         .map(|(_, color)| RawU16::from(color).into_inner()),
                 )
             } else {
-                // Some pixels are on screen
-                self.draw_raw_iter(
-                    x0,
-                    y0,
-                    x1,
-                    y1,
-                    area.points()
-                        .zip(colors)
-                        .filter(|(point, _)| drawable_area.contains(*point))
-                        .map(|(_, color)| RawU16::from(color).into_inner()),
-                )
+                // Some pixels are on screen, send them one row at a time so
+                // that only the visible part of each row is transferred
+                let width = area.size.width as usize;
+                let skip_left = (drawable_area.top_left.x - area.top_left.x) as usize;
+                let visible = drawable_area.size.width as usize;
+                let skip_right = width - skip_left - visible;
+
+                let mut colors = colors.into_iter();
+                for y in area.rows() {
+                    if y > drawable_bottom_right.y {
+                        break;
+                    }
+                    if y < drawable_area.top_left.y {
+                        colors.by_ref().take(width).for_each(drop);
+                        continue;
+                    }
+
+                    colors.by_ref().take(skip_left).for_each(drop);
+                    let y = y as u16;
+                    self.draw_raw_iter(
+                        x0,
+                        y,
+                        x1,
+                        y,
+                        colors
+                            .by_ref()
+                            .take(visible)
+                            .map(|color| RawU16::from(color).into_inner()),
+                    )?;
+                    colors.by_ref().take(skip_right).for_each(drop);
+                }
+                Ok(())
             }
         } else {
             // No pixels are on screen
@@ -80,3 +162,40 @@ where
         self.clear_screen(RawU16::from(color).into_inner())
     }
 }
+
+impl<IFACE, RESET> OriginDimensions for &mut Ili9341<IFACE, RESET> {
+    fn size(&self) -> Size {
+        (**self).size()
+    }
+}
+
+impl<IFACE, RESET> DrawTarget for &mut Ili9341<IFACE, RESET>
+where
+    IFACE: display_interface::WriteOnlyDataCommand,
+{
+    type Error = display_interface::DisplayError;
+
+    type Color = Rgb565;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        (**self).draw_iter(pixels)
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        (**self).fill_contiguous(area, colors)
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        (**self).fill_solid(area, color)
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        (**self).clear(color)
+    }
+}
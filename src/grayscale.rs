@@ -0,0 +1,80 @@
+use crate::color::{luminance_rgb565, rgb888_to_rgb565};
+use crate::Ili9341;
+use embedded_graphics_core::{
+    pixelcolor::{raw::RawU16, Rgb565},
+    prelude::*,
+    primitives::Rectangle,
+};
+
+/// [DrawTarget] wrapper that draws everything in shades of gray
+///
+/// Each color is replaced by a gray of the same perceived brightness,
+/// computed with integer math by [luminance_rgb565].
+pub struct GrayscaleDisplay<D> {
+    inner: D,
+}
+
+impl<D> GrayscaleDisplay<D> {
+    pub fn new(inner: D) -> Self {
+        GrayscaleDisplay { inner }
+    }
+
+    /// Release the wrapped draw target
+    pub fn into_inner(self) -> D {
+        self.inner
+    }
+}
+
+fn to_gray(color: Rgb565) -> Rgb565 {
+    let luminance = luminance_rgb565(RawU16::from(color).into_inner());
+    RawU16::new(rgb888_to_rgb565(luminance, luminance, luminance)).into()
+}
+
+impl<D: Dimensions> Dimensions for GrayscaleDisplay<D> {
+    fn bounding_box(&self) -> Rectangle {
+        self.inner.bounding_box()
+    }
+}
+
+impl<D> DrawTarget for GrayscaleDisplay<D>
+where
+    D: DrawTarget<Color = Rgb565>,
+{
+    type Error = D::Error;
+
+    type Color = Rgb565;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        self.inner.draw_iter(
+            pixels
+                .into_iter()
+                .map(|Pixel(point, color)| Pixel(point, to_gray(color))),
+        )
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        self.inner
+            .fill_contiguous(area, colors.into_iter().map(to_gray))
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        self.inner.fill_solid(area, to_gray(color))
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.inner.clear(to_gray(color))
+    }
+}
+
+impl<IFACE, RESET> Ili9341<IFACE, RESET> {
+    /// Borrow the display as a [DrawTarget] that draws in shades of gray
+    pub fn as_grayscale(&mut self) -> GrayscaleDisplay<&mut Self> {
+        GrayscaleDisplay::new(self)
+    }
+}
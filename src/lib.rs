@@ -25,27 +25,121 @@
 //! ```
 //!
 //! [display-interface-spi crate]: https://crates.io/crates/display-interface-spi
+
+#[cfg(feature = "std")]
+extern crate std;
+
+use core::marker::PhantomData;
+
 use embedded_hal::delay::DelayNs;
-use embedded_hal::digital::OutputPin;
+use embedded_hal::digital::{InputPin, OutputPin};
 
 use display_interface::DataFormat;
 use display_interface::WriteOnlyDataCommand;
 
-#[cfg(feature = "graphics")]
+#[cfg(feature = "floating-point")]
+mod aa_line;
+mod arc;
+#[cfg(feature = "async")]
+mod async_driver;
+mod benchmark;
+mod bitmap;
+mod builder;
+pub mod color;
+pub mod commands;
+mod dma;
+mod double_buffer;
+mod draw;
+mod gamma;
+mod glyph;
+#[cfg(feature = "graphics-core")]
 mod graphics_core;
+#[cfg(feature = "graphics-core")]
+mod grayscale;
+mod logging;
+mod macros;
+#[cfg(test)]
+mod mock;
+#[cfg(feature = "nvm-write")]
+mod nvm;
+#[cfg(feature = "graphics-core")]
+mod partial_window;
+mod read;
+#[cfg(feature = "recorder")]
+mod recorder;
+mod shapes;
+mod small_string;
+mod stream;
+mod test_pattern;
+mod tile;
+#[cfg(feature = "trace")]
+mod trace;
+mod window;
+
+#[cfg(all(feature = "async", feature = "graphics-core"))]
+pub use async_driver::AsyncDrawTarget;
+#[cfg(feature = "async")]
+pub use async_driver::{AsyncDrawRaw, AsyncIli9341};
+pub use benchmark::BenchmarkInterface;
+pub use bitmap::PaletteU16;
+pub use builder::{AddressOffset, Ili9341Builder, ResetTiming};
+pub use dma::{DmaCapableInterface, DmaHandle};
+pub use double_buffer::DoubleBuffer;
+pub use gamma::{GammaCurve, NEGATIVE_GAMMA_COMMON, POSITIVE_GAMMA_COMMON};
+#[cfg(feature = "std")]
+pub use glyph::{rasterize_font, RasterizedFont};
+pub use glyph::{BitmapFont, GlyphAtlas};
+#[cfg(feature = "graphics-core")]
+pub use grayscale::GrayscaleDisplay;
+pub use logging::{LogSink, LoggingInterface};
+#[cfg(feature = "nvm-write")]
+pub use nvm::{NvmAddress, NvmWriteToken};
+#[cfg(feature = "graphics-core")]
+pub use partial_window::PartialDisplayWindow;
+pub use read::{
+    DisplayId, DisplayPowerMode, DisplayStatus, NvmStatus, ReadableInterface, SpiFrequencyHint,
+    DEFAULT_CONNECT_RETRIES, SPI_READ_MAX_HZ, SPI_WRITE_MAX_HZ,
+};
+#[cfg(feature = "recorder")]
+pub use recorder::{CommandRecorder, RecordedCommand, MAX_RECORDED_COMMANDS, MAX_RECORDED_DATA};
+pub use small_string::SmallString;
+pub use stream::RawPixelWriter;
+#[cfg(feature = "embedded-io")]
+pub use stream::RawPixelWriterError;
+pub use test_pattern::TestPattern;
+pub use tile::TileRenderer;
+#[cfg(feature = "trace")]
+pub use trace::{TraceEvent, TraceHook};
+pub use window::DisplayWindow;
 
+/// SPI mode with idle-low clock (CPOL=0, CPHA=0), which works with most boards
 pub use embedded_hal::spi::MODE_0 as SPI_MODE;
+/// SPI mode with idle-high clock (CPOL=1, CPHA=1)
+///
+/// The ILI9341 samples data on the rising edge of SCL, so both this mode and
+/// [SPI_MODE] are valid. Some boards and level shifters only work reliably with
+/// the clock idling high; if the display shows garbled output, try this mode.
+pub use embedded_hal::spi::MODE_3 as SPI_MODE_3;
 
 pub use display_interface::DisplayError;
 
 type Result<T = (), E = DisplayError> = core::result::Result<T, E>;
 
+/// Number of pixels [Ili9341::draw_raw_slice] copies to the stack for each
+/// [DataFormat::U16BE] transfer
+pub(crate) const SLICE_CHUNK_PIXELS: usize = 256;
+
 /// Trait that defines display size information
 pub trait DisplaySize {
     /// Width in pixels
     const WIDTH: usize;
     /// Height in pixels
     const HEIGHT: usize;
+
+    /// Mode used by [Ili9341::new_default_mode], portrait unless overridden
+    fn default_mode() -> CustomMode {
+        CustomMode::portrait()
+    }
 }
 
 /// Generic display size of 240x320 pixels
@@ -64,6 +158,14 @@ impl DisplaySize for DisplaySize320x480 {
     const HEIGHT: usize = 480;
 }
 
+/// Display size of `W`x`H` pixels, specified with const generics
+pub struct ConstDisplaySize<const W: usize, const H: usize>;
+
+impl<const W: usize, const H: usize> DisplaySize for ConstDisplaySize<W, H> {
+    const WIDTH: usize = W;
+    const HEIGHT: usize = H;
+}
+
 /// For quite a few boards (ESP32-S2-Kaluga-1, M5Stack, M5Core2 and others),
 /// the ILI9341 initialization command arguments are slightly different
 ///
@@ -102,12 +204,182 @@ impl Mode for Orientation {
     }
 }
 
+/// A [Mode] with an arbitrary MADCTL byte, for boards that need
+/// values not covered by [Orientation]
+#[derive(Clone, Copy)]
+pub struct CustomMode {
+    /// Value written to the Memory Access Control register
+    pub madctl_byte: u8,
+    /// Whether this mode swaps rows and columns
+    pub is_landscape: bool,
+}
+
+impl CustomMode {
+    /// Same MADCTL byte as [Orientation::Portrait]
+    pub fn portrait() -> Self {
+        Orientation::Portrait.into()
+    }
+
+    /// Same MADCTL byte as [Orientation::Landscape]
+    pub fn landscape() -> Self {
+        Orientation::Landscape.into()
+    }
+}
+
+impl Default for CustomMode {
+    fn default() -> Self {
+        Self::portrait()
+    }
+}
+
+impl From<Orientation> for CustomMode {
+    fn from(orientation: Orientation) -> Self {
+        CustomMode {
+            madctl_byte: orientation.mode(),
+            is_landscape: orientation.is_landscape(),
+        }
+    }
+}
+
+impl Mode for CustomMode {
+    fn mode(&self) -> u8 {
+        self.madctl_byte
+    }
+
+    fn is_landscape(&self) -> bool {
+        self.is_landscape
+    }
+}
+
+/// Individual bits of the memory access control register (MADCTL), e.g. to
+/// mirror the display without rotating it
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MadctlConfig {
+    /// Rows are written bottom to top (MY)
+    pub row_address_order: bool,
+    /// Columns are written right to left (MX)
+    pub column_address_order: bool,
+    /// Rows and columns are exchanged (MV)
+    pub row_column_exchange: bool,
+    /// The panel is refreshed bottom to top (ML)
+    pub vertical_refresh_order: bool,
+    /// Pixels are in BGR order
+    pub rgb_bgr_order: bool,
+    /// The panel is refreshed right to left (MH)
+    pub horizontal_refresh_order: bool,
+}
+
+impl MadctlConfig {
+    /// The MADCTL byte with these bits set
+    pub fn to_byte(self) -> u8 {
+        let flag = |set: bool, bit: u8| if set { bit } else { 0 };
+        flag(self.row_address_order, 0x80)
+            | flag(self.column_address_order, 0x40)
+            | flag(self.row_column_exchange, 0x20)
+            | flag(self.vertical_refresh_order, 0x10)
+            | flag(self.rgb_bgr_order, 0x08)
+            | flag(self.horizontal_refresh_order, 0x04)
+    }
+}
+
+impl From<u8> for MadctlConfig {
+    fn from(byte: u8) -> Self {
+        MadctlConfig {
+            row_address_order: byte & 0x80 != 0,
+            column_address_order: byte & 0x40 != 0,
+            row_column_exchange: byte & 0x20 != 0,
+            vertical_refresh_order: byte & 0x10 != 0,
+            rgb_bgr_order: byte & 0x08 != 0,
+            horizontal_refresh_order: byte & 0x04 != 0,
+        }
+    }
+}
+
+impl From<Orientation> for MadctlConfig {
+    fn from(orientation: Orientation) -> Self {
+        orientation.mode().into()
+    }
+}
+
+impl Mode for MadctlConfig {
+    fn mode(&self) -> u8 {
+        self.to_byte()
+    }
+
+    fn is_landscape(&self) -> bool {
+        self.row_column_exchange
+    }
+}
+
+/// Order of the color channels of the panel, selected by the BGR bit of
+/// MADCTL
+///
+/// Most ILI9341 modules use a BGR panel, which is what the modes of
+/// [Orientation] select. If red and blue are swapped on screen, the panel is
+/// wired RGB: use [ColorOrder::Rgb] with [Ili9341::set_color_order] or
+/// [Ili9341Builder::with_color_order].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorOrder {
+    Rgb,
+    Bgr,
+}
+
+impl ColorOrder {
+    /// Apply this color order to the MADCTL byte `madctl`
+    fn apply(self, madctl: u8) -> u8 {
+        match self {
+            ColorOrder::Rgb => madctl & !0x08,
+            ColorOrder::Bgr => madctl | 0x08,
+        }
+    }
+
+    /// Color order selected by the MADCTL byte `madctl`
+    fn from_madctl(madctl: u8) -> Self {
+        if madctl & 0x08 != 0 {
+            ColorOrder::Bgr
+        } else {
+            ColorOrder::Rgb
+        }
+    }
+}
+
+/// Format of the pixel data sent to the display memory
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PixelFormat {
+    /// 16 bits per pixel (rgb565), sent as 2 bytes
+    Bits16 = 0x55,
+    /// 18 bits per pixel (rgb666), sent as 3 bytes
+    Bits18 = 0x66,
+}
+
+/// Order in which the panel refreshes its rows (MADCTL ML bit)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VerticalScan {
+    TopToBottom,
+    BottomToTop,
+}
+
+/// Order in which the panel refreshes the pixels of a row (MADCTL MH bit)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HorizontalScan {
+    LeftToRight,
+    RightToLeft,
+}
+
 /// Specify state of specific mode of operation
 pub enum ModeState {
     On,
     Off,
 }
 
+/// State of an [Ili9341] that is out of sleep mode, the default
+///
+/// Only an awake display can be drawn to, see [Ili9341::sleep_in].
+pub struct Awake;
+
+/// State of an [Ili9341] in sleep mode, returned by [Ili9341::sleep_in]
+pub struct Asleep;
+
 /// There are two method for drawing to the screen:
 /// [Ili9341::draw_raw_iter] and [Ili9341::draw_raw_slice]
 ///
@@ -123,12 +395,24 @@ pub enum ModeState {
 /// - As soon as a pixel is received, an internal counter is incremented,
 ///   and the next word will fill the next pixel (the adjacent on the right, or
 ///   the first of the next row if the row ended)
-pub struct Ili9341<IFACE, RESET> {
+///
+/// `STATE` is [Awake] or [Asleep], and tracks the sleep mode at compile time:
+/// drawing methods are only available on an awake display.
+pub struct Ili9341<IFACE, RESET, STATE = Awake> {
     interface: IFACE,
     reset: RESET,
     width: usize,
     height: usize,
     landscape: bool,
+    madctl: u8,
+    pixel_format: PixelFormat,
+    scroll_offset: u16,
+    address_offset: AddressOffset,
+    inverted: bool,
+    color_order: ColorOrder,
+    #[cfg(feature = "trace")]
+    trace_hook: Option<TraceHook>,
+    _state: PhantomData<STATE>,
 }
 
 impl<IFACE, RESET> Ili9341<IFACE, RESET>
@@ -141,54 +425,31 @@ where
         reset: RESET,
         delay: &mut DELAY,
         mode: MODE,
-        _display_size: SIZE,
+        display_size: SIZE,
     ) -> Result<Self>
     where
         DELAY: DelayNs,
         SIZE: DisplaySize,
         MODE: Mode,
     {
-        let mut ili9341 = Ili9341 {
-            interface,
-            reset,
-            width: SIZE::WIDTH,
-            height: SIZE::HEIGHT,
-            landscape: false,
-        };
-
-        // Do hardware reset by holding reset low for at least 10us
-        ili9341.reset.set_low().map_err(|_| DisplayError::RSError)?;
-        let _ = delay.delay_ms(1);
-        // Set high for normal operation
-        ili9341
-            .reset
-            .set_high()
-            .map_err(|_| DisplayError::RSError)?;
-
-        // Wait 5ms after reset before sending commands
-        // and 120ms before sending Sleep Out
-        let _ = delay.delay_ms(5);
-
-        // Do software reset
-        ili9341.command(Command::SoftwareReset, &[])?;
-
-        // Wait 5ms after reset before sending commands
-        // and 120ms before sending Sleep Out
-        let _ = delay.delay_ms(120);
-
-        ili9341.set_orientation(mode)?;
-
-        // Set pixel format to 16 bits per pixel
-        ili9341.command(Command::PixelFormatSet, &[0x55])?;
-
-        ili9341.sleep_mode(ModeState::Off)?;
-
-        // Wait 5ms after Sleep Out before sending commands
-        let _ = delay.delay_ms(5);
-
-        ili9341.display_mode(ModeState::On)?;
+        Ili9341Builder::new(interface, reset)
+            .with_orientation(mode)
+            .with_display_size(display_size)
+            .build(delay)
+    }
 
-        Ok(ili9341)
+    /// Same as [Ili9341::new], using [DisplaySize::default_mode] as the mode
+    pub fn new_default_mode<DELAY, SIZE>(
+        interface: IFACE,
+        reset: RESET,
+        delay: &mut DELAY,
+        display_size: SIZE,
+    ) -> Result<Self>
+    where
+        DELAY: DelayNs,
+        SIZE: DisplaySize,
+    {
+        Self::new(interface, reset, delay, SIZE::default_mode(), display_size)
     }
 }
 
@@ -197,22 +458,100 @@ where
     IFACE: WriteOnlyDataCommand,
 {
     fn command(&mut self, cmd: Command, args: &[u8]) -> Result {
-        self.interface.send_commands(DataFormat::U8(&[cmd as u8]))?;
+        self.write_command_raw(cmd as u8, args)
+    }
+
+    /// Send a command byte from [commands] followed by its arguments
+    ///
+    /// This bypasses the driver entirely, so it is up to the caller to keep
+    /// the display in a state consistent with what the driver expects.
+    pub fn write_command_raw(&mut self, cmd: u8, args: &[u8]) -> Result {
+        self.interface.send_commands(DataFormat::U8(&[cmd]))?;
         self.interface.send_data(DataFormat::U8(args))
     }
 
     fn write_iter<I: IntoIterator<Item = u16>>(&mut self, data: I) -> Result {
-        self.command(Command::MemoryWrite, &[])?;
-        use DataFormat::U16BEIter;
-        self.interface.send_data(U16BEIter(&mut data.into_iter()))
+        self.write_pixels(Command::MemoryWrite, data)
+    }
+
+    /// Send `cmd`, starting or continuing a memory write, followed by `data`
+    fn write_pixels<I: IntoIterator<Item = u16>>(&mut self, cmd: Command, data: I) -> Result {
+        self.command(cmd, &[])?;
+        match self.pixel_format {
+            PixelFormat::Bits16 => {
+                use DataFormat::U16BEIter;
+                self.interface.send_data(U16BEIter(&mut data.into_iter()))
+            }
+            PixelFormat::Bits18 => {
+                self.write_data_u8(data.into_iter().flat_map(color::rgb565_to_rgb666))
+            }
+        }
     }
 
-    fn write_slice(&mut self, data: &[u16]) -> Result {
+    /// Send raw data bytes, continuing the transaction opened by the last command
+    fn write_data_u8<I: IntoIterator<Item = u8>>(&mut self, data: I) -> Result {
+        use DataFormat::U8Iter;
+        self.interface.send_data(U8Iter(&mut data.into_iter()))
+    }
+
+    /// Send `rows` one after the other as [DataFormat::U16BE] transfers
+    ///
+    /// [DataFormat::U16BE] needs a mutable slice, since interfaces swap the
+    /// bytes in place, so the pixels go through a stack buffer of
+    /// [SLICE_CHUNK_PIXELS] pixels. Interfaces can send each chunk in bulk
+    /// instead of pulling pixels out of an iterator one at a time.
+    fn write_slices<'a, R: IntoIterator<Item = &'a [u16]>>(&mut self, rows: R) -> Result {
+        if self.pixel_format == PixelFormat::Bits18 {
+            return self.write_iter(rows.into_iter().flatten().copied());
+        }
         self.command(Command::MemoryWrite, &[])?;
-        self.interface.send_data(DataFormat::U16(data))
+        let mut buf = [0; SLICE_CHUNK_PIXELS];
+        for chunk in rows
+            .into_iter()
+            .flat_map(|row| row.chunks(SLICE_CHUNK_PIXELS))
+        {
+            let buf = &mut buf[..chunk.len()];
+            buf.copy_from_slice(chunk);
+            self.interface.send_data(DataFormat::U16BE(buf))?;
+        }
+        Ok(())
+    }
+
+    /// Send more pixels to the window of the last drawing call, starting
+    /// where it stopped (3Ch)
+    ///
+    /// This allows sending a large window in chunks, e.g. from a DMA buffer,
+    /// after starting it with [Ili9341::draw_raw_iter] or
+    /// [Ili9341::draw_raw_slice]. Any other command sent in between, like
+    /// setting a new window, resets the write address, and the next call
+    /// starts over from the top-left corner of the window.
+    pub fn write_pixels_continue<I: IntoIterator<Item = u16>>(&mut self, data: I) -> Result {
+        self.write_pixels(Command::WriteMemoryContinue, data)
+    }
+
+    /// Change the format of the pixels sent to the display
+    ///
+    /// Drawing methods keep taking rgb565 values, which are expanded
+    /// to 3 bytes per pixel when [PixelFormat::Bits18] is selected.
+    pub fn set_pixel_format(&mut self, format: PixelFormat) -> Result {
+        self.command(Command::PixelFormatSet, &[format as u8])?;
+        self.pixel_format = format;
+        Ok(())
     }
 
     fn set_window(&mut self, x0: u16, y0: u16, x1: u16, y1: u16) -> Result {
+        let AddressOffset {
+            column_offset,
+            page_offset,
+        } = self.address_offset;
+        let (x0, x1) = (
+            x0.saturating_add(column_offset),
+            x1.saturating_add(column_offset),
+        );
+        let (y0, y1) = (
+            y0.saturating_add(page_offset),
+            y1.saturating_add(page_offset),
+        );
         self.command(
             Command::ColumnAddressSet,
             &[
@@ -233,18 +572,37 @@ where
         )
     }
 
+    /// Set the drawing window like `set_window`, clamping all coordinates to
+    /// the display bounds
+    ///
+    /// Returns the corners of the window actually set, or
+    /// [DisplayError::OutOfBoundsError] without setting anything if the
+    /// window is entirely off screen or its corners are swapped.
+    pub fn set_window_clamped(
+        &mut self,
+        x0: u16,
+        y0: u16,
+        x1: u16,
+        y1: u16,
+    ) -> Result<(u16, u16, u16, u16)> {
+        let (x0, y0, x1, y1) = self
+            .clip_to_screen(x0, y0, x1, y1)
+            .ok_or(DisplayError::OutOfBoundsError)?;
+        self.set_window(x0, y0, x1, y1)?;
+        Ok((x0, y0, x1, y1))
+    }
+
     /// Configures the screen for hardware-accelerated vertical scrolling.
     pub fn configure_vertical_scroll(
         &mut self,
         fixed_top_lines: u16,
         fixed_bottom_lines: u16,
     ) -> Result<Scroller> {
-        let height = if self.landscape {
-            self.width
-        } else {
-            self.height
-        } as u16;
-        let scroll_lines = height as u16 - fixed_top_lines - fixed_bottom_lines;
+        let height = self.scroll_height();
+        let scroll_lines = height
+            .checked_sub(fixed_top_lines)
+            .and_then(|lines| lines.checked_sub(fixed_bottom_lines))
+            .ok_or(DisplayError::OutOfBoundsError)?;
 
         self.command(
             Command::VerticalScrollDefine,
@@ -262,19 +620,47 @@ where
     }
 
     pub fn scroll_vertically(&mut self, scroller: &mut Scroller, num_lines: u16) -> Result {
-        scroller.top_offset += num_lines;
-        if scroller.top_offset > (scroller.height - scroller.fixed_bottom_lines) {
-            scroller.top_offset = scroller.fixed_top_lines
-                + (scroller.top_offset + scroller.fixed_bottom_lines - scroller.height)
+        scroller.top_offset = scroller.advance(scroller.top_offset, num_lines.into());
+        self.write_scroll_offset(scroller.top_offset)
+    }
+
+    /// Scroll to an absolute line, as yielded by [ScrollIter]
+    ///
+    /// `line` must be inside the scroll area, otherwise
+    /// [DisplayError::OutOfBoundsError] is returned.
+    pub fn scroll_to_line(&mut self, scroller: &mut Scroller, line: u16) -> Result {
+        scroller.check_line(line)?;
+        scroller.top_offset = line;
+        self.write_scroll_offset(line)
+    }
+
+    /// Write the vertical scrolling start address (VSCSAD) directly,
+    /// bypassing [Scroller]
+    ///
+    /// Returns [DisplayError::OutOfBoundsError] if `vscsad` is not a line of
+    /// the display.
+    pub fn set_scroll_offset_raw(&mut self, vscsad: u16) -> Result {
+        if vscsad >= self.scroll_height() {
+            return Err(DisplayError::OutOfBoundsError);
         }
+        self.write_scroll_offset(vscsad)
+    }
+
+    /// Get the last scroll offset written to the display
+    ///
+    /// The value is cached by the driver, as the display does not allow
+    /// reading it back.
+    pub fn get_scroll_offset_raw(&self) -> u16 {
+        self.scroll_offset
+    }
 
+    fn write_scroll_offset(&mut self, top_offset: u16) -> Result {
         self.command(
             Command::VerticalScrollAddr,
-            &[
-                (scroller.top_offset >> 8) as u8,
-                (scroller.top_offset & 0xff) as u8,
-            ],
-        )
+            &[(top_offset >> 8) as u8, (top_offset & 0xff) as u8],
+        )?;
+        self.scroll_offset = top_offset;
+        Ok(())
     }
 
     /// Draw a rectangle on the screen, represented by top-left corner (x0, y0)
@@ -286,6 +672,11 @@ where
     ///
     /// The iterator is useful to avoid wasting memory by holding a buffer for
     /// the whole screen when it is not necessary.
+    ///
+    /// A rectangle that doesn't fit on the screen is cut at the display
+    /// bounds: the pixels falling outside of it are skipped, and nothing is
+    /// sent if no pixel is visible. With the `bounds-check` feature,
+    /// [DisplayError::OutOfBoundsError] is returned instead.
     pub fn draw_raw_iter<I: IntoIterator<Item = u16>>(
         &mut self,
         x0: u16,
@@ -294,8 +685,44 @@ where
         y1: u16,
         data: I,
     ) -> Result {
-        self.set_window(x0, y0, x1, y1)?;
-        self.write_iter(data)
+        #[cfg(feature = "trace")]
+        self.trace_draw_start(x0, y0, x1, y1);
+
+        let result = match self.visible_window(x0, y0, x1, y1) {
+            Ok(Some(visible)) => self
+                .set_window(visible.0, visible.1, visible.2, visible.3)
+                .and_then(|()| {
+                    let data = ClippedPixels::new(data.into_iter(), (x0, y0, x1, y1), visible);
+                    self.write_iter(data)
+                }),
+            Ok(None) => Ok(()),
+            Err(e) => Err(e),
+        };
+
+        #[cfg(feature = "trace")]
+        self.trace_draw_end();
+
+        result
+    }
+
+    /// Same as [Ili9341::draw_raw_iter], but checks that the iterator yields
+    /// exactly one pixel for every point of the rectangle.
+    ///
+    /// Returns [DisplayError::InvalidFormatError] without drawing anything if
+    /// the lengths don't match, or [DisplayError::OutOfBoundsError] if the
+    /// corners are swapped.
+    pub fn draw_raw_iter_exact<I: ExactSizeIterator<Item = u16>>(
+        &mut self,
+        x0: u16,
+        y0: u16,
+        x1: u16,
+        y1: u16,
+        data: I,
+    ) -> Result {
+        if data.len() != window_size(x0, y0, x1, y1)? {
+            return Err(DisplayError::InvalidFormatError);
+        }
+        self.draw_raw_iter(x0, y0, x1, y1, data)
     }
 
     /// Draw a rectangle on the screen, represented by top-left corner (x0, y0)
@@ -307,9 +734,119 @@ where
     /// video memory.
     ///
     /// The expected format is rgb565.
+    ///
+    /// Like [Ili9341::draw_raw_iter], the rectangle is clipped to the screen
+    /// bounds, or rejected with [DisplayError::OutOfBoundsError] with the
+    /// `bounds-check` feature. A clipped rectangle is sent one visible row
+    /// at a time.
     pub fn draw_raw_slice(&mut self, x0: u16, y0: u16, x1: u16, y1: u16, data: &[u16]) -> Result {
+        match self.visible_window(x0, y0, x1, y1)? {
+            Some(visible) => {
+                self.set_window(visible.0, visible.1, visible.2, visible.3)?;
+                if visible == (x0, y0, x1, y1) {
+                    self.write_slices(Some(data))
+                } else {
+                    self.write_slices(visible_rows(data, (x0, y0, x1, y1), visible))
+                }
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Draw a rectangle on the screen, represented by top-left corner (x0, y0)
+    /// and bottom-right corner (x1, y1).
+    ///
+    /// The border is included.
+    ///
+    /// This method accepts raw bytes that are sent to the screen video memory
+    /// unchanged, so they must already be in the current [PixelFormat]
+    /// (e.g. big endian rgb565 for [PixelFormat::Bits16]).
+    pub fn draw_raw_bytes(&mut self, x0: u16, y0: u16, x1: u16, y1: u16, data: &[u8]) -> Result {
         self.set_window(x0, y0, x1, y1)?;
-        self.write_slice(data)
+        self.command(Command::MemoryWrite, &[])?;
+        self.interface.send_data(DataFormat::U8(data))
+    }
+
+    /// Draw a rectangle on the screen, represented by top-left corner (x0, y0)
+    /// and bottom-right corner (x1, y1), from pixels in column-major order
+    ///
+    /// The MV bit of MADCTL is flipped while the pixels are sent, so that the
+    /// display fills the rectangle column by column and no transposition is
+    /// needed. The previous MADCTL value is restored afterwards, even if
+    /// sending fails.
+    pub fn draw_raw_columns<I: IntoIterator<Item = u16>>(
+        &mut self,
+        x0: u16,
+        y0: u16,
+        x1: u16,
+        y1: u16,
+        data: I,
+    ) -> Result {
+        let madctl = self.madctl;
+        let offset = self.address_offset;
+
+        // With MV flipped, logical columns and pages are exchanged
+        self.address_offset = AddressOffset {
+            column_offset: offset.page_offset,
+            page_offset: offset.column_offset,
+        };
+        let result = self
+            .command(Command::MemoryAccessControl, &[madctl ^ 0x20])
+            .and_then(|()| self.set_window(y0, x0, y1, x1))
+            .and_then(|()| self.write_iter(data));
+
+        self.address_offset = offset;
+        let restored = self.command(Command::MemoryAccessControl, &[madctl]);
+        result.and(restored)
+    }
+
+    /// Same as [Ili9341::draw_raw_iter], with the rectangle given as a
+    /// [DisplayWindow]
+    ///
+    /// Returns [DisplayError::OutOfBoundsError] if the corners are swapped.
+    pub fn draw_raw_iter_window<W, I>(&mut self, window: W, data: I) -> Result
+    where
+        W: Into<DisplayWindow>,
+        I: IntoIterator<Item = u16>,
+    {
+        let DisplayWindow { x0, y0, x1, y1 } = window.into();
+        window_size(x0, y0, x1, y1)?;
+        self.draw_raw_iter(x0, y0, x1, y1, data)
+    }
+
+    /// Same as [Ili9341::draw_raw_slice], with the rectangle given as a
+    /// [DisplayWindow]
+    ///
+    /// Returns [DisplayError::OutOfBoundsError] if the corners are swapped.
+    pub fn draw_raw_slice_window<W: Into<DisplayWindow>>(
+        &mut self,
+        window: W,
+        data: &[u16],
+    ) -> Result {
+        let DisplayWindow { x0, y0, x1, y1 } = window.into();
+        window_size(x0, y0, x1, y1)?;
+        self.draw_raw_slice(x0, y0, x1, y1, data)
+    }
+
+    /// Draw a rectangle from pixel data that lives in flash for the whole
+    /// program, e.g. a `static` image.
+    ///
+    /// The `'static` lifetime guarantees that the buffer outlives any DMA
+    /// transfer the interface may start, so it can be sent without a copy.
+    ///
+    /// This relies on flash being mapped in the normal address space, as on
+    /// ARM and other von Neumann architectures. On Harvard-architecture MCUs
+    /// such as AVR, flash must be accessed through special instructions and
+    /// this method must not be used with data placed in program memory.
+    pub fn draw_bitmap_from_flash(
+        &mut self,
+        x0: u16,
+        y0: u16,
+        x1: u16,
+        y1: u16,
+        flash_data: &'static [u16],
+    ) -> Result {
+        self.draw_raw_slice(x0, y0, x1, y1, flash_data)
     }
 
     /// Change the orientation of the screen
@@ -317,27 +854,150 @@ where
     where
         MODE: Mode,
     {
-        self.command(Command::MemoryAccessControl, &[mode.mode()])?;
+        let madctl = self.color_order.apply(mode.mode());
+        self.set_address_mode(madctl, mode.is_landscape())
+    }
 
-        if self.landscape ^ mode.is_landscape() {
-            core::mem::swap(&mut self.height, &mut self.width);
-        }
-        self.landscape = mode.is_landscape();
+    /// Write MADCTL and set whether rows and columns are exchanged
+    ///
+    /// Width and height are swapped when `swap_dimensions` differs from the
+    /// current [Ili9341::is_landscape]. This is the primitive the other
+    /// orientation methods are built on.
+    pub fn set_address_mode(&mut self, madctl: u8, swap_dimensions: bool) -> Result {
+        self.command(Command::MemoryAccessControl, &[madctl])?;
+        self.address_mode_written(madctl, swap_dimensions);
         Ok(())
     }
 
+    /// Write MADCTL from its individual bits
+    ///
+    /// Width and height are swapped according to
+    /// [MadctlConfig::row_column_exchange].
+    pub fn set_madctl(&mut self, cfg: MadctlConfig) -> Result {
+        self.set_address_mode(cfg.to_byte(), cfg.row_column_exchange)
+    }
+
+    /// Write MADCTL directly, for boards that need values [Orientation]
+    /// doesn't cover
+    ///
+    /// The width and height are swapped according to the MV bit (0x20).
+    pub fn set_memory_access_control_raw(&mut self, byte: u8) -> Result {
+        self.set_address_mode(byte, byte & 0x20 != 0)
+    }
+
+    /// Select the color order of the panel, leaving the orientation as is
+    ///
+    /// Only the BGR bit of MADCTL is changed, and the color order is kept
+    /// by later calls to [Ili9341::set_orientation].
+    pub fn set_color_order(&mut self, order: ColorOrder) -> Result {
+        self.set_address_mode(order.apply(self.madctl), self.landscape)
+    }
+
+    /// Get the color order of the panel, see [ColorOrder]
+    pub fn color_order(&self) -> ColorOrder {
+        self.color_order
+    }
+
+    /// Get the last MADCTL value written by the driver
+    pub fn memory_access_control_raw(&self) -> u8 {
+        self.madctl
+    }
+
+    /// Physical memory location, as (column, page), of the pixel at (x, y)
+    ///
+    /// The coordinates sent with ColumnAddressSet and PageAddressSet are
+    /// logical: the display maps them to memory according to the MV
+    /// (exchange), MX (column mirror) and MY (page mirror) bits of MADCTL.
+    /// This applies the same mapping, including the [AddressOffset], which
+    /// helps checking custom modes and rendering code.
+    pub fn pixel_to_gram_address(&self, x: u16, y: u16) -> (u16, u16) {
+        let x = x.saturating_add(self.address_offset.column_offset);
+        let y = y.saturating_add(self.address_offset.page_offset);
+        let (columns, pages) = if self.landscape {
+            (self.height, self.width)
+        } else {
+            (self.width, self.height)
+        };
+        let (mut column, mut page) = if self.madctl & 0x20 != 0 {
+            (y, x)
+        } else {
+            (x, y)
+        };
+        if self.madctl & 0x40 != 0 {
+            column = (columns as u16).wrapping_sub(1).wrapping_sub(column);
+        }
+        if self.madctl & 0x80 != 0 {
+            page = (pages as u16).wrapping_sub(1).wrapping_sub(page);
+        }
+        (column, page)
+    }
+
+    /// Temporarily switch to another orientation while `f` runs, e.g. to
+    /// draw a rotated label
+    ///
+    /// The previous orientation and color order are restored afterwards, even
+    /// if `f` fails.
+    pub fn with_orientation<MODE, F, R>(&mut self, mode: MODE, f: F) -> Result<R>
+    where
+        MODE: Mode,
+        F: FnOnce(&mut Self) -> Result<R>,
+    {
+        let (madctl, landscape) = (self.madctl, self.landscape);
+        let (width, height) = (self.width, self.height);
+        let color_order = self.color_order;
+
+        self.set_orientation(mode)?;
+        let result = f(self);
+
+        let restored = self.command(Command::MemoryAccessControl, &[madctl]);
+        self.madctl = madctl;
+        self.landscape = landscape;
+        self.width = width;
+        self.height = height;
+        self.color_order = color_order;
+
+        let value = result?;
+        restored.map(|_| value)
+    }
+
+    /// Change the refresh order of the panel, leaving the orientation as is
+    ///
+    /// Only the ML and MH bits of MADCTL are changed. Refreshing in the same
+    /// direction pixels are written in can remove visible flicker on some
+    /// panels.
+    pub fn set_scan_direction(
+        &mut self,
+        vertical: VerticalScan,
+        horizontal: HorizontalScan,
+    ) -> Result {
+        let mut madctl = self.madctl & !(0x10 | 0x04);
+        if vertical == VerticalScan::BottomToTop {
+            madctl |= 0x10;
+        }
+        if horizontal == HorizontalScan::RightToLeft {
+            madctl |= 0x04;
+        }
+        self.set_address_mode(madctl, self.landscape)
+    }
+
     /// Fill entire screen with specfied color u16 value
     pub fn clear_screen(&mut self, color: u16) -> Result {
         let color = core::iter::repeat(color).take(self.width * self.height);
-        self.draw_raw_iter(0, 0, self.width as u16, self.height as u16, color)
+        let x1 = self.width.saturating_sub(1).min(u16::MAX as usize) as u16;
+        let y1 = self.height.saturating_sub(1).min(u16::MAX as usize) as u16;
+        self.draw_raw_iter(0, 0, x1, y1, color)
     }
 
-    /// Control the screen sleep mode:
-    pub fn sleep_mode(&mut self, mode: ModeState) -> Result {
-        match mode {
-            ModeState::On => self.command(Command::SleepModeOn, &[]),
-            ModeState::Off => self.command(Command::SleepModeOff, &[]),
+    /// Fill a rectangle, represented by top-left corner (x0, y0) and
+    /// bottom-right corner (x1, y1), with `color`
+    ///
+    /// The border is included. Nothing is sent if the corners are swapped.
+    pub fn clear_region(&mut self, x0: u16, y0: u16, x1: u16, y1: u16, color: u16) -> Result {
+        if x0 > x1 || y0 > y1 {
+            return Ok(());
         }
+        let pixels = window_size(x0, y0, x1, y1)?;
+        self.draw_raw_iter(x0, y0, x1, y1, core::iter::repeat(color).take(pixels))
     }
 
     /// Control the screen display mode
@@ -348,12 +1008,113 @@ where
         }
     }
 
+    /// Show the display memory again after [Ili9341::display_off]
+    pub fn display_on(&mut self) -> Result {
+        self.display_mode(ModeState::On)
+    }
+
+    /// Blank the screen immediately, e.g. while redrawing it entirely
+    ///
+    /// Unlike [Ili9341::sleep_in], the controller stays powered: the display
+    /// memory can still be written, and [Ili9341::display_on] shows it again
+    /// without the 120ms wait required after leaving sleep mode. Sleep mode
+    /// saves a lot more power.
+    pub fn display_off(&mut self) -> Result {
+        self.display_mode(ModeState::Off)
+    }
+
+    /// Enter sleep mode, to save power while the display is not used
+    ///
+    /// The display memory is kept, but writing pixels while asleep leads to
+    /// undefined behavior of the display, so the returned display can't be
+    /// drawn to until [Ili9341::sleep_out] is called. This waits the 5ms the
+    /// datasheet requires before the next command; it also requires 120ms
+    /// before waking up.
+    pub fn sleep_in<DELAY: DelayNs>(
+        mut self,
+        delay: &mut DELAY,
+    ) -> Result<Ili9341<IFACE, RESET, Asleep>> {
+        self.command(Command::SleepModeOn, &[])?;
+        delay.delay_ms(5);
+        Ok(self.into_state())
+    }
+
     /// Invert the pixel color on screen
     pub fn invert_mode(&mut self, mode: ModeState) -> Result {
+        let inverted = matches!(mode, ModeState::On);
         match mode {
-            ModeState::On => self.command(Command::InvertOn, &[]),
-            ModeState::Off => self.command(Command::InvertOff, &[]),
+            ModeState::On => self.command(Command::InvertOn, &[])?,
+            ModeState::Off => self.command(Command::InvertOff, &[])?,
         }
+        self.inverted = inverted;
+        Ok(())
+    }
+
+    /// Invert all colors on screen, without redrawing anything
+    pub fn inversion_on(&mut self) -> Result {
+        self.invert_mode(ModeState::On)
+    }
+
+    /// Show colors normally again after [Ili9341::inversion_on]
+    pub fn inversion_off(&mut self) -> Result {
+        self.invert_mode(ModeState::Off)
+    }
+
+    /// Switch color inversion on or off, depending on its current state
+    pub fn toggle_inversion(&mut self) -> Result {
+        if self.inverted {
+            self.inversion_off()
+        } else {
+            self.inversion_on()
+        }
+    }
+
+    /// Whether colors are currently inverted
+    pub fn is_inverted(&self) -> bool {
+        self.inverted
+    }
+
+    /// Turn every pixel white, ignoring the display memory, e.g. to check
+    /// the panel for dead pixels
+    ///
+    /// Use [Ili9341::normal_display_mode] to show the memory again.
+    pub fn all_pixels_on(&mut self) -> Result {
+        self.command(Command::AllPixelsOn, &[])
+    }
+
+    /// Turn every pixel black, ignoring the display memory
+    ///
+    /// Use [Ili9341::normal_display_mode] to show the memory again.
+    pub fn all_pixels_off(&mut self) -> Result {
+        self.command(Command::AllPixelsOff, &[])
+    }
+
+    /// Show the whole display memory, leaving partial mode and the all
+    /// pixels on/off modes
+    pub fn normal_display_mode(&mut self) -> Result {
+        self.command(Command::NormalDisplayModeOn, &[])
+    }
+
+    /// Only refresh the rows from `row_start` to `row_end`, inclusive, to
+    /// save power; the rest of the panel shows the non-display area color
+    ///
+    /// Rows are gate lines of the panel, the same lines as vertical
+    /// scrolling, which are columns in a landscape orientation. Returns
+    /// [DisplayError::OutOfBoundsError] if `row_end` is before `row_start` or
+    /// either is outside of the panel.
+    pub fn enable_partial_mode(&mut self, row_start: u16, row_end: u16) -> Result {
+        if row_end < row_start || row_end >= self.scroll_height() {
+            return Err(DisplayError::OutOfBoundsError);
+        }
+        let [start_hi, start_lo] = row_start.to_be_bytes();
+        let [end_hi, end_lo] = row_end.to_be_bytes();
+        self.command(Command::PartialArea, &[start_hi, start_lo, end_hi, end_lo])?;
+        self.command(Command::PartialModeOn, &[])
+    }
+
+    /// Refresh the whole panel again after [Ili9341::enable_partial_mode]
+    pub fn disable_partial_mode(&mut self) -> Result {
+        self.command(Command::NormalDisplayModeOn, &[])
     }
 
     /// Idle mode reduces the number of colors to 8
@@ -364,11 +1125,45 @@ where
         }
     }
 
+    /// Enter idle mode, where only the most significant bit of each color
+    /// channel is shown, for 8 colors at a much lower power consumption
+    ///
+    /// The display must not be in sleep mode when entering idle mode, wake it
+    /// up with [Ili9341::sleep_out] first. Pixels are still sent as rgb565,
+    /// the display discards the lower bits.
+    pub fn idle_mode_on(&mut self) -> Result {
+        self.idle_mode(ModeState::On)
+    }
+
+    /// Leave idle mode and show all colors again
+    pub fn idle_mode_off(&mut self) -> Result {
+        self.idle_mode(ModeState::Off)
+    }
+
     /// Set display brightness to the value between 0 and 255
     pub fn brightness(&mut self, brightness: u8) -> Result {
         self.command(Command::SetBrightness, &[brightness])
     }
 
+    /// Same as [Ili9341::brightness]
+    pub fn set_brightness(&mut self, level: u8) -> Result {
+        self.brightness(level)
+    }
+
+    /// Write the CTRL display register (53h), with the brightness control
+    /// (BCTRL, 0x20), dimming (DD, 0x08) and backlight (BL, 0x04) bits
+    ///
+    /// Brightness set with [Ili9341::set_brightness] is only applied when
+    /// BCTRL is set.
+    pub fn set_ctrl_display(&mut self, flags: u8) -> Result {
+        self.command(Command::WriteCtrlDisplay, &[flags])
+    }
+
+    /// Select the content adaptive brightness control mode (55h)
+    pub fn set_cabc_mode(&mut self, mode: CabcMode) -> Result {
+        self.command(Command::ContentAdaptiveBrightness, &[mode as _])
+    }
+
     /// Set adaptive brightness value equal to [AdaptiveBrightness]
     pub fn content_adaptive_brightness(&mut self, value: AdaptiveBrightness) -> Result {
         self.command(Command::ContentAdaptiveBrightness, &[value as _])
@@ -394,22 +1189,451 @@ where
     ) -> Result {
         self.command(Command::IdleModeFrameRate, &[clk_div as _, frame_rate as _])
     }
+
+    /// Set the frame rate in normal mode (B1h)
+    ///
+    /// Returns [DisplayError::OutOfBoundsError] if a field of `config` is
+    /// outside of the range allowed by the datasheet.
+    pub fn set_frame_rate_normal(&mut self, config: FrameRateConfig) -> Result {
+        self.frame_rate_command(Command::NormalModeFrameRate, config)
+    }
+
+    /// Set the frame rate in idle mode (B2h), see
+    /// [Ili9341::set_frame_rate_normal]
+    pub fn set_frame_rate_idle(&mut self, config: FrameRateConfig) -> Result {
+        self.frame_rate_command(Command::IdleModeFrameRate, config)
+    }
+
+    /// Set the frame rate in partial mode (B3h), see
+    /// [Ili9341::set_frame_rate_normal]
+    pub fn set_frame_rate_partial(&mut self, config: FrameRateConfig) -> Result {
+        self.frame_rate_command(Command::PartialModeFrameRate, config)
+    }
+
+    fn frame_rate_command(&mut self, cmd: Command, config: FrameRateConfig) -> Result {
+        if config.division_ratio > 0x03 || !(0x10..=0x1f).contains(&config.fosc_clocks) {
+            return Err(DisplayError::OutOfBoundsError);
+        }
+        self.command(cmd, &[config.division_ratio, config.fosc_clocks])
+    }
+
+    /// Set the GVDD level (C0h), the reference for the grayscale voltages
+    ///
+    /// Returns [DisplayError::OutOfBoundsError] if `vrh` is not between 0x03
+    /// and 0x3f.
+    pub fn set_power_control_1(&mut self, vrh: u8) -> Result {
+        if !(0x03..=0x3f).contains(&vrh) {
+            return Err(DisplayError::OutOfBoundsError);
+        }
+        self.command(Command::PowerControl1, &[vrh])
+    }
+
+    /// Set the step-up factor of the power supply circuits (C1h)
+    ///
+    /// Lower factors reduce the power consumption, but may not drive the
+    /// panel properly. Returns [DisplayError::OutOfBoundsError] if `bt` is
+    /// above 7.
+    pub fn set_power_control_2(&mut self, bt: u8) -> Result {
+        if bt > 0x07 {
+            return Err(DisplayError::OutOfBoundsError);
+        }
+        self.command(Command::PowerControl2, &[0x10 | bt])
+    }
+
+    /// Set both power control registers, see [Ili9341::set_power_control_1]
+    /// and [Ili9341::set_power_control_2]
+    pub fn set_power_config(&mut self, config: PowerConfig) -> Result {
+        self.set_power_control_1(config.vrh)?;
+        self.set_power_control_2(config.bt)
+    }
+
+    /// Set the VCOM high and low voltages (C5h), which affect flicker and
+    /// contrast
+    ///
+    /// `vmh` and `vml` are the register values from the datasheet, returns
+    /// [DisplayError::OutOfBoundsError] if either is above 0x7f.
+    pub fn set_vcom_control_1(&mut self, vmh: u8, vml: u8) -> Result {
+        if vmh > 0x7f || vml > 0x7f {
+            return Err(DisplayError::OutOfBoundsError);
+        }
+        self.command(Command::VcomControl1, &[vmh, vml])
+    }
+
+    /// Set the VCOM offset (C7h)
+    ///
+    /// `offset` is sent unchanged: the offset in the lower 7 bits (VMF) is
+    /// only applied when bit 7 (nVM) is set.
+    pub fn set_vcom_control_2(&mut self, offset: u8) -> Result {
+        self.command(Command::VcomControl2, &[offset])
+    }
+
+    /// Configure the blanking porches (B5h)
+    ///
+    /// Returns [DisplayError::OutOfBoundsError] if a value is outside of the
+    /// range allowed by the datasheet.
+    pub fn set_porch_control(&mut self, porch: PorchControl) -> Result {
+        let vertical = 2..=127;
+        let horizontal = 2..=31;
+        if !vertical.contains(&porch.vfp)
+            || !vertical.contains(&porch.vbp)
+            || !horizontal.contains(&porch.hfp)
+            || !horizontal.contains(&porch.hbp)
+        {
+            return Err(DisplayError::OutOfBoundsError);
+        }
+        self.command(
+            Command::BlankingPorchControl,
+            &[porch.vfp, porch.vbp, porch.hfp, porch.hbp],
+        )
+    }
+
+    /// Configure the display function control register (B6h)
+    ///
+    /// Returns [DisplayError::OutOfBoundsError] if a field doesn't fit in
+    /// its bits.
+    pub fn set_display_function(&mut self, dfc: DisplayFunctionControl) -> Result {
+        if dfc.ptg > 0x03 || dfc.pt > 0x03 || dfc.isc > 0x0f || dfc.nl > 0x3f || dfc.pcdiv > 0x3f {
+            return Err(DisplayError::OutOfBoundsError);
+        }
+        let flag = |set: bool, bit: u8| if set { bit } else { 0 };
+        self.command(
+            Command::DisplayFunctionControl,
+            &[
+                (dfc.ptg << 2) | dfc.pt,
+                flag(dfc.rev, 0x80)
+                    | flag(dfc.gs, 0x40)
+                    | flag(dfc.ss, 0x20)
+                    | flag(dfc.sm, 0x10)
+                    | dfc.isc,
+                dfc.nl,
+                dfc.pcdiv,
+            ],
+        )
+    }
+
+    /// Turn the tearing effect output on, signaling the vertical blanking
+    /// period, and optionally the horizontal one, on the TE pin
+    pub fn enable_tearing_effect(&mut self, mode: TearingMode) -> Result {
+        self.command(Command::TearingEffectLineOn, &[mode as _])
+    }
+
+    /// Turn the tearing effect output off, the TE pin stays low
+    pub fn disable_tearing_effect(&mut self) -> Result {
+        self.command(Command::TearingEffectLineOff, &[])
+    }
+
+    /// Same as [Ili9341::set_te_scanline]
+    pub fn set_tearing_scanline(&mut self, line: u16) -> Result {
+        self.set_te_scanline(line)
+    }
+
+    /// Make the tearing effect signal fire when the display scan reaches `line`
+    ///
+    /// The tearing effect output must be enabled for this to have any effect.
+    pub fn set_te_scanline(&mut self, line: u16) -> Result {
+        self.command(
+            Command::SetTearScanline,
+            &[(line >> 8) as u8, (line & 0xff) as u8],
+        )
+    }
+
+    /// Wait for the display to release a BUSY pin, polling it every microsecond
+    ///
+    /// Returns [DisplayError::BusWriteError] if the pin is still high after
+    /// `timeout_us` microseconds, or if it cannot be read.
+    pub fn wait_ready<BUSY, DELAY>(
+        &mut self,
+        busy_pin: &mut BUSY,
+        delay: &mut DELAY,
+        timeout_us: u32,
+    ) -> Result
+    where
+        BUSY: InputPin,
+        DELAY: DelayNs,
+    {
+        let mut remaining_us = timeout_us;
+        while busy_pin
+            .is_high()
+            .map_err(|_| DisplayError::BusWriteError)?
+        {
+            if remaining_us == 0 {
+                return Err(DisplayError::BusWriteError);
+            }
+            delay.delay_us(1);
+            remaining_us -= 1;
+        }
+        Ok(())
+    }
 }
 
-impl<IFACE, RESET> Ili9341<IFACE, RESET> {
+impl<IFACE, RESET, STATE> Ili9341<IFACE, RESET, STATE> {
     /// Get the current screen width. It can change based on the current orientation
+    #[inline]
     pub fn width(&self) -> usize {
         self.width
     }
 
     /// Get the current screen heighth. It can change based on the current orientation
+    #[inline]
     pub fn height(&self) -> usize {
         self.height
     }
+
+    /// Get a reference to the display interface
+    pub fn interface(&self) -> &IFACE {
+        &self.interface
+    }
+
+    /// Get a mutable reference to the display interface
+    pub fn interface_mut(&mut self) -> &mut IFACE {
+        &mut self.interface
+    }
+
+    /// Get a mutable reference to the reset pin
+    pub fn reset_pin_mut(&mut self) -> &mut RESET {
+        &mut self.reset
+    }
+
+    /// Release the display interface and the reset pin
+    ///
+    /// They can be passed to [Ili9341Builder::from] to initialize the display
+    /// again.
+    pub fn into_parts(self) -> (IFACE, RESET) {
+        (self.interface, self.reset)
+    }
+
+    /// Same as [Ili9341::into_parts]
+    pub fn into_inner(self) -> (IFACE, RESET) {
+        self.into_parts()
+    }
+
+    /// Replace the display interface, keeping the reset pin and the state
+    /// cached by the driver, and return the previous interface
+    ///
+    /// Nothing is sent to the display, so the new interface must talk to the
+    /// same, already initialized, display.
+    pub fn swap_interface<IFACE2>(
+        self,
+        new_iface: IFACE2,
+    ) -> (Ili9341<IFACE2, RESET, STATE>, IFACE) {
+        let display = Ili9341 {
+            interface: new_iface,
+            reset: self.reset,
+            width: self.width,
+            height: self.height,
+            landscape: self.landscape,
+            madctl: self.madctl,
+            pixel_format: self.pixel_format,
+            scroll_offset: self.scroll_offset,
+            address_offset: self.address_offset,
+            inverted: self.inverted,
+            color_order: self.color_order,
+            #[cfg(feature = "trace")]
+            trace_hook: self.trace_hook,
+            _state: PhantomData,
+        };
+        (display, self.interface)
+    }
+
+    /// Change the sleep state tracked by the type, without sending anything
+    fn into_state<STATE2>(self) -> Ili9341<IFACE, RESET, STATE2> {
+        Ili9341 {
+            interface: self.interface,
+            reset: self.reset,
+            width: self.width,
+            height: self.height,
+            landscape: self.landscape,
+            madctl: self.madctl,
+            pixel_format: self.pixel_format,
+            scroll_offset: self.scroll_offset,
+            address_offset: self.address_offset,
+            inverted: self.inverted,
+            color_order: self.color_order,
+            #[cfg(feature = "trace")]
+            trace_hook: self.trace_hook,
+            _state: PhantomData,
+        }
+    }
+
+    /// Get the pixel format currently used to send pixels to the display
+    pub fn pixel_format(&self) -> PixelFormat {
+        self.pixel_format
+    }
+
+    /// Whether the current orientation swaps rows and columns
+    pub fn is_landscape(&self) -> bool {
+        self.landscape
+    }
+
+    /// Whether the current orientation keeps the native row and column order
+    pub fn is_portrait(&self) -> bool {
+        !self.landscape
+    }
+
+    /// Intersection of the rectangle with top-left corner (x0, y0) and
+    /// bottom-right corner (x1, y1) with the screen, or `None` if it is empty
+    fn clip_to_screen(&self, x0: u16, y0: u16, x1: u16, y1: u16) -> Option<(u16, u16, u16, u16)> {
+        let max_x = self.width.checked_sub(1)?.min(u16::MAX as usize) as u16;
+        let max_y = self.height.checked_sub(1)?.min(u16::MAX as usize) as u16;
+        if x1 < x0 || y1 < y0 || x0 > max_x || y0 > max_y {
+            return None;
+        }
+        Some((x0, y0, x1.min(max_x), y1.min(max_y)))
+    }
+
+    /// The part of a rectangle drawn by [Ili9341::draw_raw_iter] that is
+    /// sent to the display, see [Ili9341::clip_to_screen]
+    ///
+    /// With the `bounds-check` feature, a rectangle that doesn't fit on the
+    /// screen is an error instead.
+    fn visible_window(
+        &self,
+        x0: u16,
+        y0: u16,
+        x1: u16,
+        y1: u16,
+    ) -> Result<Option<(u16, u16, u16, u16)>> {
+        if cfg!(feature = "bounds-check")
+            && (usize::from(x0.max(x1)) >= self.width || usize::from(y0.max(y1)) >= self.height)
+        {
+            return Err(DisplayError::OutOfBoundsError);
+        }
+        Ok(self.clip_to_screen(x0, y0, x1, y1))
+    }
+
+    /// Update the state after `madctl` has been written to MADCTL, see
+    /// [Ili9341::set_address_mode]
+    fn address_mode_written(&mut self, madctl: u8, swap_dimensions: bool) {
+        self.madctl = madctl;
+        self.color_order = ColorOrder::from_madctl(madctl);
+
+        if self.landscape ^ swap_dimensions {
+            core::mem::swap(&mut self.height, &mut self.width);
+        }
+        self.landscape = swap_dimensions;
+    }
+
+    /// Number of lines in the vertical scroll direction
+    fn scroll_height(&self) -> u16 {
+        let height = if self.landscape {
+            self.width
+        } else {
+            self.height
+        };
+        height as u16
+    }
+}
+
+impl<IFACE, RESET> Ili9341<IFACE, RESET, Asleep>
+where
+    IFACE: WriteOnlyDataCommand,
+{
+    /// Leave sleep mode and wait the 120ms the datasheet requires before any
+    /// other command
+    pub fn sleep_out<DELAY: DelayNs>(self, delay: &mut DELAY) -> Result<Ili9341<IFACE, RESET>> {
+        let mut display = self.into_state();
+        display.command(Command::SleepModeOff, &[])?;
+        delay.delay_ms(120);
+        Ok(display)
+    }
+
+    /// Leave sleep mode, wait the 120ms required by the datasheet and turn
+    /// the display on
+    ///
+    /// This is the recommended way to wake the display up if it was turned
+    /// off with [Ili9341::display_off] before sleeping.
+    pub fn sleep_out_and_wait<DELAY: DelayNs>(
+        self,
+        delay: &mut DELAY,
+    ) -> Result<Ili9341<IFACE, RESET>> {
+        let mut display = self.sleep_out(delay)?;
+        display.display_on()?;
+        Ok(display)
+    }
+}
+
+/// Pixels of a rectangle, in drawing order, restricted to its visible part
+///
+/// Rows are cut after the visible columns and the rows below the visible
+/// part are not read, so pixels keep their place in the image.
+struct ClippedPixels<I> {
+    data: I,
+    /// Width of the whole rectangle
+    width: usize,
+    /// Width of the visible part, starting from the left of the rectangle
+    visible_width: usize,
+    /// Column of the next pixel of `data`
+    column: usize,
+    /// Number of pixels of `data` left to read
+    remaining: usize,
+}
+
+impl<I: Iterator<Item = u16>> ClippedPixels<I> {
+    /// `visible` must be the result of [Ili9341::clip_to_screen] for `rect`
+    fn new(data: I, rect: (u16, u16, u16, u16), visible: (u16, u16, u16, u16)) -> Self {
+        let (x0, y0, x1, _) = rect;
+        let width = usize::from(x1 - x0) + 1;
+        let rows = usize::from(visible.3 - y0) + 1;
+        ClippedPixels {
+            data,
+            width,
+            visible_width: usize::from(visible.2 - x0) + 1,
+            column: 0,
+            remaining: width * rows,
+        }
+    }
+}
+
+impl<I: Iterator<Item = u16>> Iterator for ClippedPixels<I> {
+    type Item = u16;
+
+    fn next(&mut self) -> Option<u16> {
+        loop {
+            if self.remaining == 0 {
+                return None;
+            }
+            self.remaining -= 1;
+            let column = self.column;
+            self.column = if column + 1 == self.width {
+                0
+            } else {
+                column + 1
+            };
+            let pixel = self.data.next()?;
+            if column < self.visible_width {
+                return Some(pixel);
+            }
+        }
+    }
+}
+
+/// Rows of the visible part of a rectangle stored row by row in `data`, see
+/// [ClippedPixels]
+fn visible_rows(
+    data: &[u16],
+    rect: (u16, u16, u16, u16),
+    visible: (u16, u16, u16, u16),
+) -> impl Iterator<Item = &[u16]> {
+    let (x0, y0, x1, _) = rect;
+    let visible_width = usize::from(visible.2 - x0) + 1;
+    data.chunks(usize::from(x1 - x0) + 1)
+        .take(usize::from(visible.3 - y0) + 1)
+        .map(move |row| &row[..row.len().min(visible_width)])
+}
+
+/// Number of pixels in the window with top-left corner (x0, y0) and
+/// bottom-right corner (x1, y1), or [DisplayError::OutOfBoundsError] if the
+/// corners are swapped
+fn window_size(x0: u16, y0: u16, x1: u16, y1: u16) -> Result<usize> {
+    if x1 < x0 || y1 < y0 {
+        return Err(DisplayError::OutOfBoundsError);
+    }
+    Ok((usize::from(x1 - x0) + 1) * (usize::from(y1 - y0) + 1))
 }
 
 /// Scroller must be provided in order to scroll the screen. It can only be obtained
 /// by configuring the screen for scrolling.
+#[derive(Clone)]
 pub struct Scroller {
     top_offset: u16,
     fixed_bottom_lines: u16,
@@ -426,6 +1650,79 @@ impl Scroller {
             height,
         }
     }
+
+    fn scroll_lines(&self) -> u16 {
+        self.height - self.fixed_top_lines - self.fixed_bottom_lines
+    }
+
+    /// Return [DisplayError::OutOfBoundsError] if `line` is outside the
+    /// scroll area
+    pub(crate) fn check_line(&self, line: u16) -> Result {
+        if line < self.fixed_top_lines || line >= self.height - self.fixed_bottom_lines {
+            return Err(DisplayError::OutOfBoundsError);
+        }
+        Ok(())
+    }
+
+    /// Move `top_offset` forward by `num_lines`, wrapping inside the scroll area.
+    ///
+    /// Computed in u32 so that no intermediate value can overflow.
+    fn advance(&self, top_offset: u16, num_lines: u32) -> u16 {
+        let scroll_lines = u32::from(self.scroll_lines());
+        if scroll_lines == 0 {
+            return top_offset;
+        }
+        let offset = u32::from(top_offset - self.fixed_top_lines);
+        self.fixed_top_lines + ((offset + num_lines) % scroll_lines) as u16
+    }
+}
+
+/// Iterator over the absolute scroll offsets of an animation that scrolls
+/// `total_lines` lines, `lines_per_frame` lines at a time.
+///
+/// Every item is meant to be passed to [Ili9341::scroll_to_line]:
+///
+/// ```ignore
+/// for line in ScrollIter::new(&scroller, 2, 320) {
+///     display.scroll_to_line(&mut scroller, line)?;
+/// }
+/// ```
+///
+/// Use [Iterator::cycle] to repeat the animation forever.
+#[derive(Clone)]
+pub struct ScrollIter {
+    scroller: Scroller,
+    lines_per_frame: u16,
+    total_lines: u16,
+    consumed: u16,
+}
+
+impl ScrollIter {
+    /// Start scrolling from the current offset of `scroller`
+    pub fn new(scroller: &Scroller, lines_per_frame: u16, total_lines: u16) -> Self {
+        ScrollIter {
+            scroller: scroller.clone(),
+            lines_per_frame,
+            total_lines,
+            consumed: 0,
+        }
+    }
+}
+
+impl Iterator for ScrollIter {
+    type Item = u16;
+
+    fn next(&mut self) -> Option<u16> {
+        if self.lines_per_frame == 0 || self.consumed >= self.total_lines {
+            return None;
+        }
+        self.consumed = self
+            .consumed
+            .saturating_add(self.lines_per_frame)
+            .min(self.total_lines);
+        let scroller = &self.scroller;
+        Some(scroller.advance(scroller.top_offset, self.consumed.into()))
+    }
 }
 
 /// Available Adaptive Brightness values
@@ -436,6 +1733,24 @@ pub enum AdaptiveBrightness {
     MovingImage = 0x03,
 }
 
+/// Content adaptive brightness control modes, for [Ili9341::set_cabc_mode]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CabcMode {
+    Off = 0x00,
+    UiMode = 0x01,
+    StillPicture = 0x02,
+    MovingImage = 0x03,
+}
+
+/// Events signaled on the TE pin by [Ili9341::enable_tearing_effect]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TearingMode {
+    /// Only the vertical blanking period
+    VBlankOnly = 0x00,
+    /// Both the vertical and horizontal blanking periods
+    VBlankAndHBlank = 0x01,
+}
+
 /// Available frame rate in Hz
 pub enum FrameRate {
     FrameRate119 = 0x10,
@@ -456,6 +1771,54 @@ pub enum FrameRate {
     FrameRate61 = 0x1f,
 }
 
+/// Raw frame rate control parameters, see [FrameRateHz] for common values
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FrameRateConfig {
+    /// Division ratio of the internal clock (DIVA), 0 to 3 for fosc divided
+    /// by 1, 2, 4 or 8
+    pub division_ratio: u8,
+    /// Clocks per line (RTNA), 0x10 to 0x1f for 16 to 31 clocks
+    pub fosc_clocks: u8,
+}
+
+impl Default for FrameRateConfig {
+    /// The reset values from the datasheet, 70Hz
+    fn default() -> Self {
+        FrameRateConfig {
+            division_ratio: 0x00,
+            fosc_clocks: 0x1b,
+        }
+    }
+}
+
+/// Common frame rates, with the default 320 lines and porches
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FrameRateHz {
+    Hz61,
+    Hz70,
+    Hz79,
+    Hz90,
+    Hz100,
+    Hz119,
+}
+
+impl From<FrameRateHz> for FrameRateConfig {
+    fn from(rate: FrameRateHz) -> Self {
+        let fosc_clocks = match rate {
+            FrameRateHz::Hz61 => FrameRate::FrameRate61,
+            FrameRateHz::Hz70 => FrameRate::FrameRate70,
+            FrameRateHz::Hz79 => FrameRate::FrameRate79,
+            FrameRateHz::Hz90 => FrameRate::FrameRate90,
+            FrameRateHz::Hz100 => FrameRate::FrameRate100,
+            FrameRateHz::Hz119 => FrameRate::FrameRate119,
+        };
+        FrameRateConfig {
+            division_ratio: FrameRateClockDivision::Fosc as _,
+            fosc_clocks: fosc_clocks as _,
+        }
+    }
+}
+
 /// Frame rate clock division
 pub enum FrameRateClockDivision {
     Fosc = 0x00,
@@ -464,26 +1827,310 @@ pub enum FrameRateClockDivision {
     FoscDiv8 = 0x03,
 }
 
+/// Blanking porch lengths, in lines for the vertical porches and in clocks
+/// for the horizontal ones
+///
+/// Longer porches make a longer frame, lowering the frame rate set with
+/// [Ili9341::normal_mode_frame_rate].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PorchControl {
+    /// Vertical front porch, 2 to 127
+    pub vfp: u8,
+    /// Vertical back porch, 2 to 127
+    pub vbp: u8,
+    /// Horizontal front porch, 2 to 31
+    pub hfp: u8,
+    /// Horizontal back porch, 2 to 31
+    pub hbp: u8,
+}
+
+impl Default for PorchControl {
+    /// The reset values from the datasheet
+    fn default() -> Self {
+        PorchControl {
+            vfp: 0x02,
+            vbp: 0x02,
+            hfp: 0x0a,
+            hbp: 0x14,
+        }
+    }
+}
+
+/// Parameters of the power control registers (C0h and C1h)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PowerConfig {
+    /// GVDD level (VRH), 0x03 to 0x3f
+    pub vrh: u8,
+    /// Step-up factor (BT), 0 to 7
+    pub bt: u8,
+}
+
+impl Default for PowerConfig {
+    /// The reset values from the datasheet, which are kept by [Ili9341::new]
+    fn default() -> Self {
+        PowerConfig {
+            vrh: 0x21,
+            bt: 0x00,
+        }
+    }
+}
+
+/// Parameters of the display function control register (B6h)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DisplayFunctionControl {
+    /// Scan mode of the gate driver in the non-display area (PTG), 0 to 3
+    pub ptg: u8,
+    /// Source and VCOM output in the non-display area (PT), 0 to 3
+    pub pt: u8,
+    /// Liquid crystal type, `true` for normally white
+    pub rev: bool,
+    /// Gate output scan direction
+    pub gs: bool,
+    /// Source output scan direction
+    pub ss: bool,
+    /// Gate driver pin arrangement, combined with `gs`
+    pub sm: bool,
+    /// Scan cycle in the non-display area (ISC), 0 to 15
+    pub isc: u8,
+    /// Number of lines to drive, in units of 8 lines minus one, 0 to 63
+    pub nl: u8,
+    /// External clock divider (PCDIV), 0 to 63
+    pub pcdiv: u8,
+}
+
+impl DisplayFunctionControl {
+    /// The reset values from the datasheet, driving 320 lines
+    pub const DEFAULT: DisplayFunctionControl = DisplayFunctionControl {
+        ptg: 0b10,
+        pt: 0b10,
+        rev: true,
+        gs: false,
+        ss: false,
+        sm: false,
+        isc: 0b0010,
+        nl: 0x27,
+        pcdiv: 0,
+    };
+}
+
+impl Default for DisplayFunctionControl {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
 #[derive(Clone, Copy)]
 enum Command {
     SoftwareReset = 0x01,
+    ReadDisplayIdInfo = 0x04,
+    ReadDisplayStatus = 0x09,
+    ReadDisplayPowerMode = 0x0a,
+    ReadDisplayMadctl = 0x0b,
     MemoryAccessControl = 0x36,
     PixelFormatSet = 0x3a,
     SleepModeOn = 0x10,
     SleepModeOff = 0x11,
+    PartialModeOn = 0x12,
+    NormalDisplayModeOn = 0x13,
     InvertOff = 0x20,
     InvertOn = 0x21,
+    AllPixelsOff = 0x22,
+    AllPixelsOn = 0x23,
+    GammaSet = 0x26,
     DisplayOff = 0x28,
     DisplayOn = 0x29,
     ColumnAddressSet = 0x2a,
     PageAddressSet = 0x2b,
     MemoryWrite = 0x2c,
+    MemoryRead = 0x2e,
+    PartialArea = 0x30,
     VerticalScrollDefine = 0x33,
+    TearingEffectLineOff = 0x34,
+    TearingEffectLineOn = 0x35,
     VerticalScrollAddr = 0x37,
     IdleModeOff = 0x38,
     IdleModeOn = 0x39,
+    WriteMemoryContinue = 0x3c,
+    ReadMemoryContinue = 0x3e,
+    SetTearScanline = 0x44,
+    GetScanline = 0x45,
     SetBrightness = 0x51,
+    ReadBrightness = 0x52,
+    WriteCtrlDisplay = 0x53,
     ContentAdaptiveBrightness = 0x55,
     NormalModeFrameRate = 0xb1,
     IdleModeFrameRate = 0xb2,
+    PartialModeFrameRate = 0xb3,
+    BlankingPorchControl = 0xb5,
+    DisplayFunctionControl = 0xb6,
+    PowerControl1 = 0xc0,
+    PowerControl2 = 0xc1,
+    VcomControl1 = 0xc5,
+    VcomControl2 = 0xc7,
+    #[cfg(feature = "nvm-write")]
+    NvMemoryWrite = 0xd0,
+    #[cfg(feature = "nvm-write")]
+    NvMemoryProtectionKey = 0xd1,
+    NvMemoryStatusRead = 0xd2,
+    ReadId1 = 0xda,
+    ReadId2 = 0xdb,
+    ReadId3 = 0xdc,
+    PositiveGammaCorrection = 0xe0,
+    NegativeGammaCorrection = 0xe1,
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::{ColorOrder, Orientation, TearingMode, SLICE_CHUNK_PIXELS};
+    use crate::mock::{display, take_sent};
+    use std::vec;
+
+    #[test]
+    fn idle_mode() {
+        let mut display = display();
+        display.idle_mode_on().unwrap();
+        assert_eq!(take_sent(&mut display), [(0x39, vec![])]);
+        display.idle_mode_off().unwrap();
+        assert_eq!(take_sent(&mut display), [(0x38, vec![])]);
+    }
+
+    #[test]
+    fn inversion() {
+        let mut display = display();
+        display.inversion_on().unwrap();
+        assert_eq!(take_sent(&mut display), [(0x21, vec![])]);
+        assert!(display.is_inverted());
+        display.toggle_inversion().unwrap();
+        assert_eq!(take_sent(&mut display), [(0x20, vec![])]);
+        assert!(!display.is_inverted());
+        display.all_pixels_on().unwrap();
+        display.all_pixels_off().unwrap();
+        display.normal_display_mode().unwrap();
+        assert_eq!(
+            take_sent(&mut display),
+            [(0x23, vec![]), (0x22, vec![]), (0x13, vec![])]
+        );
+    }
+
+    #[test]
+    fn partial_mode() {
+        let mut display = display();
+        display.enable_partial_mode(0x0010, 0x013f).unwrap();
+        assert_eq!(
+            take_sent(&mut display),
+            [(0x30, vec![0x00, 0x10, 0x01, 0x3f]), (0x12, vec![])]
+        );
+        display.disable_partial_mode().unwrap();
+        assert_eq!(take_sent(&mut display), [(0x13, vec![])]);
+
+        assert!(display.enable_partial_mode(20, 10).is_err());
+        assert!(display.enable_partial_mode(0, 320).is_err());
+        assert_eq!(take_sent(&mut display), []);
+    }
+
+    #[test]
+    fn partial_mode_landscape() {
+        let mut display = display();
+        display.set_orientation(Orientation::Landscape).unwrap();
+        take_sent(&mut display);
+        // The panel keeps its 320 gate lines, while the screen is 240 high
+        display.enable_partial_mode(0, 319).unwrap();
+        assert_eq!(
+            take_sent(&mut display),
+            [(0x30, vec![0x00, 0x00, 0x01, 0x3f]), (0x12, vec![])]
+        );
+        assert!(display.enable_partial_mode(0, 320).is_err());
+    }
+
+    #[test]
+    fn display_on_off() {
+        let mut display = display();
+        display.display_off().unwrap();
+        display.display_on().unwrap();
+        assert_eq!(take_sent(&mut display), [(0x28, vec![]), (0x29, vec![])]);
+    }
+
+    #[test]
+    fn tearing_scanline() {
+        let mut display = display();
+        display.set_tearing_scanline(0x0123).unwrap();
+        assert_eq!(take_sent(&mut display), [(0x44, vec![0x01, 0x23])]);
+        display.set_tearing_scanline(0x00ff).unwrap();
+        assert_eq!(take_sent(&mut display), [(0x44, vec![0x00, 0xff])]);
+    }
+
+    #[test]
+    fn tearing_effect() {
+        let mut display = display();
+        display
+            .enable_tearing_effect(TearingMode::VBlankOnly)
+            .unwrap();
+        display.disable_tearing_effect().unwrap();
+        assert_eq!(
+            take_sent(&mut display),
+            [(0x35, vec![0x00]), (0x34, vec![])]
+        );
+    }
+
+    #[test]
+    fn draw_raw_slice_chunks() {
+        let mut display = display();
+        let frame = vec![0x1234; 240 * 320];
+        display.draw_raw_slice(0, 0, 239, 319, &frame).unwrap();
+        // One call for each window command and one per chunk of pixels
+        assert_eq!(
+            display.interface_mut().data_calls,
+            3 + frame.len() / SLICE_CHUNK_PIXELS
+        );
+        let sent = take_sent(&mut display);
+        assert_eq!(sent[2].0, 0x2c);
+        assert_eq!(sent[2].1.len(), 2 * frame.len());
+        assert_eq!(sent[2].1[..2], [0x12, 0x34]);
+    }
+
+    #[test]
+    #[cfg(not(feature = "bounds-check"))]
+    fn draw_raw_slice_clips_like_draw_raw_iter() {
+        let data: std::vec::Vec<u16> = (0..24).collect();
+        let mut expected = display();
+        expected
+            .draw_raw_iter(236, 318, 241, 321, data.iter().copied())
+            .unwrap();
+        let mut display = display();
+        display.draw_raw_slice(236, 318, 241, 321, &data).unwrap();
+        assert_eq!(take_sent(&mut display), take_sent(&mut expected));
+
+        display.draw_raw_slice(240, 0, 250, 10, &data).unwrap();
+        assert_eq!(take_sent(&mut display), []);
+    }
+
+    #[test]
+    #[cfg(feature = "bounds-check")]
+    fn draw_raw_slice_bounds_check() {
+        let mut display = display();
+        assert!(display
+            .draw_raw_slice(236, 318, 241, 321, &[0; 24])
+            .is_err());
+        assert_eq!(take_sent(&mut display), []);
+    }
+
+    #[test]
+    fn with_orientation_restores_color_order() {
+        let mut display = display();
+        assert_eq!(display.color_order(), ColorOrder::Bgr);
+        display
+            .with_orientation(Orientation::Landscape, |display| {
+                display.set_color_order(ColorOrder::Rgb)
+            })
+            .unwrap();
+        assert_eq!(display.color_order(), ColorOrder::Bgr);
+        assert_eq!(display.memory_access_control_raw(), 0x48);
+        assert_eq!((display.width(), display.height()), (240, 320));
+        assert_eq!(
+            take_sent(&mut display),
+            [(0x36, vec![0x28]), (0x36, vec![0x20]), (0x36, vec![0x48])]
+        );
+    }
 }
@@ -0,0 +1,173 @@
+use display_interface::{DataFormat, DisplayError, WriteOnlyDataCommand};
+
+/// Receiver for the bytes seen by a [LoggingInterface]
+///
+/// Implemented for pairs of closures `(log_command, log_data)`:
+///
+/// ```ignore
+/// let sink = (
+///     |cmd: u8| log::info!("cmd {:02x}", cmd),
+///     |data: &[u8]| log::info!("    {:02x?}", data),
+/// );
+/// ```
+pub trait LogSink {
+    fn log_command(&mut self, cmd: u8);
+    /// Called with the data bytes in the order they are sent, possibly split
+    /// over several calls
+    fn log_data(&mut self, data: &[u8]);
+}
+
+impl<C, D> LogSink for (C, D)
+where
+    C: FnMut(u8),
+    D: FnMut(&[u8]),
+{
+    fn log_command(&mut self, cmd: u8) {
+        (self.0)(cmd)
+    }
+
+    fn log_data(&mut self, data: &[u8]) {
+        (self.1)(data)
+    }
+}
+
+impl<L: LogSink> LogSink for &mut L {
+    fn log_command(&mut self, cmd: u8) {
+        (**self).log_command(cmd)
+    }
+
+    fn log_data(&mut self, data: &[u8]) {
+        (**self).log_data(data)
+    }
+}
+
+/// Interface wrapper that reports every command and data byte to a
+/// [LogSink] before forwarding it to the wrapped interface
+///
+/// Useful to check what the display receives without a logic analyzer:
+///
+/// ```ignore
+/// let iface = LoggingInterface::new(iface, sink);
+/// let mut display = Ili9341::new(iface, reset, &mut delay, mode, size)?;
+/// ```
+pub struct LoggingInterface<IFACE, L> {
+    inner: IFACE,
+    sink: L,
+}
+
+impl<IFACE, L> LoggingInterface<IFACE, L> {
+    pub fn new(inner: IFACE, sink: L) -> Self {
+        LoggingInterface { inner, sink }
+    }
+
+    pub fn sink(&self) -> &L {
+        &self.sink
+    }
+
+    pub fn sink_mut(&mut self) -> &mut L {
+        &mut self.sink
+    }
+
+    /// Release the wrapped interface and the sink
+    pub fn into_inner(self) -> (IFACE, L) {
+        (self.inner, self.sink)
+    }
+}
+
+/// Groups bytes so that they are logged in chunks rather than one by one
+struct Chunks<F: FnMut(&[u8])> {
+    buf: [u8; 32],
+    len: usize,
+    log: F,
+}
+
+impl<F: FnMut(&[u8])> Chunks<F> {
+    fn new(log: F) -> Self {
+        Chunks {
+            buf: [0; 32],
+            len: 0,
+            log,
+        }
+    }
+
+    fn push(&mut self, byte: u8) {
+        if self.len == self.buf.len() {
+            self.flush();
+        }
+        self.buf[self.len] = byte;
+        self.len += 1;
+    }
+
+    fn push_all<I: IntoIterator<Item = u8>>(&mut self, bytes: I) {
+        bytes.into_iter().for_each(|byte| self.push(byte));
+    }
+
+    fn flush(&mut self) {
+        if self.len > 0 {
+            (self.log)(&self.buf[..self.len]);
+            self.len = 0;
+        }
+    }
+}
+
+/// Forward `data` to `send`, passing the bytes it contains to `log`
+fn logged<S, F>(data: DataFormat<'_>, send: S, log: F) -> Result<(), DisplayError>
+where
+    S: FnOnce(DataFormat<'_>) -> Result<(), DisplayError>,
+    F: FnMut(&[u8]),
+{
+    let mut chunks = Chunks::new(log);
+    let result = match data {
+        DataFormat::U8(buf) => {
+            (chunks.log)(buf);
+            send(DataFormat::U8(buf))
+        }
+        DataFormat::U16(buf) => {
+            chunks.push_all(buf.iter().flat_map(|w| w.to_ne_bytes()));
+            send(DataFormat::U16(buf))
+        }
+        DataFormat::U16BE(buf) => {
+            chunks.push_all(buf.iter().flat_map(|w| w.to_be_bytes()));
+            send(DataFormat::U16BE(buf))
+        }
+        DataFormat::U16LE(buf) => {
+            chunks.push_all(buf.iter().flat_map(|w| w.to_le_bytes()));
+            send(DataFormat::U16LE(buf))
+        }
+        DataFormat::U8Iter(iter) => {
+            let mut iter = iter.inspect(|&byte| chunks.push(byte));
+            send(DataFormat::U8Iter(&mut iter))
+        }
+        DataFormat::U16BEIter(iter) => {
+            let mut iter = iter.inspect(|w| chunks.push_all(w.to_be_bytes()));
+            send(DataFormat::U16BEIter(&mut iter))
+        }
+        DataFormat::U16LEIter(iter) => {
+            let mut iter = iter.inspect(|w| chunks.push_all(w.to_le_bytes()));
+            send(DataFormat::U16LEIter(&mut iter))
+        }
+        _ => Err(DisplayError::DataFormatNotImplemented),
+    };
+    chunks.flush();
+    result
+}
+
+impl<IFACE, L> WriteOnlyDataCommand for LoggingInterface<IFACE, L>
+where
+    IFACE: WriteOnlyDataCommand,
+    L: LogSink,
+{
+    fn send_commands(&mut self, cmd: DataFormat<'_>) -> Result<(), DisplayError> {
+        let (inner, sink) = (&mut self.inner, &mut self.sink);
+        logged(
+            cmd,
+            |cmd| inner.send_commands(cmd),
+            |cmds| cmds.iter().for_each(|&cmd| sink.log_command(cmd)),
+        )
+    }
+
+    fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        let (inner, sink) = (&mut self.inner, &mut self.sink);
+        logged(buf, |buf| inner.send_data(buf), |data| sink.log_data(data))
+    }
+}
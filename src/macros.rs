@@ -0,0 +1,36 @@
+/// Display size and orientation, as a tuple to pass to [Ili9341::new](crate::Ili9341::new)
+///
+/// The size is one of the predefined [DisplaySize](crate::DisplaySize)s and
+/// the orientation an [Orientation](crate::Orientation) variant:
+///
+/// ```ignore
+/// let (size, orientation) = display_config!(240x320, Landscape);
+/// let display = Ili9341::new(iface, reset, &mut delay, orientation, size)?;
+/// ```
+#[macro_export]
+macro_rules! display_config {
+    (240x320, $orientation:ident) => {
+        (
+            $crate::DisplaySize240x320,
+            $crate::Orientation::$orientation,
+        )
+    };
+    (320x480, $orientation:ident) => {
+        (
+            $crate::DisplaySize320x480,
+            $crate::Orientation::$orientation,
+        )
+    };
+}
+
+/// [ConstDisplaySize](crate::ConstDisplaySize) of the given width and height
+///
+/// ```ignore
+/// let display = Ili9341::new(iface, reset, &mut delay, Orientation::Portrait, display_size!(240, 280))?;
+/// ```
+#[macro_export]
+macro_rules! display_size {
+    ($width:expr, $height:expr) => {
+        $crate::ConstDisplaySize::<{ $width }, { $height }>
+    };
+}
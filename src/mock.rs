@@ -0,0 +1,178 @@
+//! Test doubles for the interface, reset pin and delay
+
+extern crate std;
+
+use std::vec::Vec;
+
+use crate::{
+    DisplaySize240x320, Ili9341, Ili9341Builder, Orientation, ReadableInterface, Result,
+    SpiFrequencyHint,
+};
+use display_interface::{DataFormat, DisplayError, WriteOnlyDataCommand};
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::{ErrorType, InputPin, OutputPin};
+
+/// Interface recording every command with the data sent after it
+#[derive(Default)]
+pub struct MockInterface {
+    pub sent: Vec<(u8, Vec<u8>)>,
+    /// Number of `send_data` calls
+    pub data_calls: usize,
+    /// Clock frequency, `None` when the interface reports no frequency hint
+    pub frequency_hz: Option<u32>,
+    /// Every frequency set through the hint
+    pub frequencies: Vec<u32>,
+}
+
+impl WriteOnlyDataCommand for MockInterface {
+    fn send_commands(&mut self, cmd: DataFormat<'_>) -> Result {
+        match cmd {
+            DataFormat::U8(cmds) => {
+                for &cmd in cmds {
+                    self.sent.push((cmd, Vec::new()));
+                }
+                Ok(())
+            }
+            _ => Err(DisplayError::DataFormatNotImplemented),
+        }
+    }
+
+    fn send_data(&mut self, buf: DataFormat<'_>) -> Result {
+        self.data_calls += 1;
+        let data = &mut self
+            .sent
+            .last_mut()
+            .ok_or(DisplayError::InvalidFormatError)?
+            .1;
+        match buf {
+            DataFormat::U8(bytes) => data.extend_from_slice(bytes),
+            DataFormat::U8Iter(iter) => data.extend(iter),
+            DataFormat::U16BE(words) => data.extend(words.iter().flat_map(|w| w.to_be_bytes())),
+            DataFormat::U16BEIter(iter) => data.extend(iter.flat_map(|w| w.to_be_bytes())),
+            _ => return Err(DisplayError::DataFormatNotImplemented),
+        }
+        Ok(())
+    }
+}
+
+impl ReadableInterface for MockInterface {
+    fn read_command(&mut self, cmd: u8, buf: &mut [u8]) -> Result {
+        self.sent.push((cmd, Vec::new()));
+        buf.fill(0);
+        Ok(())
+    }
+
+    fn frequency_hint(&mut self) -> Option<&mut dyn SpiFrequencyHint> {
+        match self.frequency_hz {
+            Some(_) => Some(self),
+            None => None,
+        }
+    }
+}
+
+impl SpiFrequencyHint for MockInterface {
+    fn frequency_hz(&self) -> Option<u32> {
+        self.frequency_hz
+    }
+
+    fn set_frequency_hz(&mut self, hz: u32) -> Result {
+        self.frequency_hz = Some(hz);
+        self.frequencies.push(hz);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "async")]
+impl display_interface::AsyncWriteOnlyDataCommand for MockInterface {
+    async fn send_commands(&mut self, cmd: DataFormat<'_>) -> Result {
+        WriteOnlyDataCommand::send_commands(self, cmd)
+    }
+
+    async fn send_data(&mut self, buf: DataFormat<'_>) -> Result {
+        WriteOnlyDataCommand::send_data(self, buf)
+    }
+}
+
+pub struct MockPin;
+
+impl ErrorType for MockPin {
+    type Error = core::convert::Infallible;
+}
+
+impl OutputPin for MockPin {
+    fn set_low(&mut self) -> core::result::Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> core::result::Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// Input pin reading `levels` in order, then staying at the last one
+pub struct MockTePin {
+    pub levels: Vec<bool>,
+    /// Number of reads so far
+    pub reads: usize,
+}
+
+impl MockTePin {
+    pub fn new(levels: &[bool]) -> Self {
+        MockTePin {
+            levels: levels.to_vec(),
+            reads: 0,
+        }
+    }
+}
+
+impl ErrorType for MockTePin {
+    type Error = core::convert::Infallible;
+}
+
+impl InputPin for MockTePin {
+    fn is_high(&mut self) -> core::result::Result<bool, Self::Error> {
+        let level = self.levels[self.reads.min(self.levels.len() - 1)];
+        self.reads += 1;
+        Ok(level)
+    }
+
+    fn is_low(&mut self) -> core::result::Result<bool, Self::Error> {
+        self.is_high().map(|high| !high)
+    }
+}
+
+pub struct MockDelay;
+
+impl DelayNs for MockDelay {
+    fn delay_ns(&mut self, _ns: u32) {}
+}
+
+/// A portrait 240x320 display, with the initialization commands cleared
+pub fn display() -> Ili9341<MockInterface, MockPin> {
+    let mut display = Ili9341Builder::new(MockInterface::default(), MockPin)
+        .with_orientation(Orientation::Portrait)
+        .with_display_size(DisplaySize240x320)
+        .build(&mut MockDelay)
+        .unwrap();
+    display.interface_mut().sent.clear();
+    display.interface_mut().data_calls = 0;
+    display
+}
+
+/// Take the commands recorded since the last call
+pub fn take_sent(display: &mut Ili9341<MockInterface, MockPin>) -> Vec<(u8, Vec<u8>)> {
+    core::mem::take(&mut display.interface_mut().sent)
+}
+
+/// Poll `future` until it completes
+#[cfg(feature = "async")]
+pub fn block_on<F: core::future::Future>(future: F) -> F::Output {
+    let waker = crate::dma::noop_waker();
+    let mut cx = core::task::Context::from_waker(&waker);
+    let mut future = core::pin::pin!(future);
+    loop {
+        if let core::task::Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
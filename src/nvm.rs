@@ -0,0 +1,124 @@
+use crate::{Command, Ili9341, Result};
+use display_interface::WriteOnlyDataCommand;
+
+/// Key sent with NV Memory Protection Key (D1h) to unlock programming
+const NVM_PROTECTION_KEY: [u8; 3] = [0x55, 0xaa, 0x66];
+
+/// Registers backed by the non-volatile memory of the display
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NvmAddress {
+    Id1 = 0b000,
+    Id2 = 0b001,
+    Id3 = 0b010,
+    /// VCOM offset (VMF)
+    VcomOffset = 0b100,
+}
+
+/// Proof that the caller really means to program the non-volatile memory,
+/// required by [Ili9341::nvm_write]
+pub struct NvmWriteToken(());
+
+impl NvmWriteToken {
+    /// Acknowledge that NVM writes are permanent
+    ///
+    /// # Dangerous
+    ///
+    /// Each NVM register can only be programmed a few times over the life of
+    /// the display, and a wrong value can't be undone. Only create a token in
+    /// code that is meant to program panels, never in regular firmware.
+    pub fn confirm() -> Self {
+        NvmWriteToken(())
+    }
+}
+
+impl<IFACE, RESET> Ili9341<IFACE, RESET>
+where
+    IFACE: WriteOnlyDataCommand,
+{
+    /// Program `data` into the non-volatile register at `address`
+    ///
+    /// Sends the protection key (D1h) followed by NV Memory Write (D0h). The
+    /// datasheet requires the programming voltage to be supplied to the
+    /// panel; completion can be checked with the NV memory status (D2h).
+    ///
+    /// # Dangerous
+    ///
+    /// NVM writes are permanent and limited to a few cycles per register.
+    /// Gamma and timing settings are not stored in NVM and must be sent at
+    /// every initialization instead.
+    pub fn nvm_write(&mut self, address: NvmAddress, data: u8, confirm: NvmWriteToken) -> Result {
+        let NvmWriteToken(()) = confirm;
+        self.command(Command::NvMemoryProtectionKey, &NVM_PROTECTION_KEY)?;
+        self.command(Command::NvMemoryWrite, &[address as u8, data])
+    }
+
+    /// Send NV Memory Write (D0h) without the protection key
+    ///
+    /// The key must have been sent with [Ili9341::nvm_protection_key] first,
+    /// e.g. to program several registers after a single key.
+    ///
+    /// # Dangerous
+    ///
+    /// Each register can only be programmed a few times over the life of the
+    /// display, check the write counters with
+    /// [Ili9341::read_nvm_status](crate::Ili9341::read_nvm_status) before
+    /// writing. Prefer [Ili9341::nvm_write], which sends the key.
+    pub fn nvm_write_unkeyed(
+        &mut self,
+        address: NvmAddress,
+        data: u8,
+        confirm: NvmWriteToken,
+    ) -> Result {
+        let NvmWriteToken(()) = confirm;
+        self.command(Command::NvMemoryWrite, &[address as u8, data])
+    }
+
+    /// Send NV Memory Protection Key (D1h), `0x55aa66` enables programming
+    ///
+    /// Only the lower 3 bytes of `key` are sent, most significant first.
+    pub fn nvm_protection_key(&mut self, key: u32) -> Result {
+        let [_, key @ ..] = key.to_be_bytes();
+        self.command(Command::NvMemoryProtectionKey, &key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::{NvmAddress, NvmWriteToken};
+    use crate::mock::{display, take_sent};
+    use std::vec;
+
+    #[test]
+    fn nvm_write() {
+        let mut display = display();
+        display
+            .nvm_write(NvmAddress::VcomOffset, 0x12, NvmWriteToken::confirm())
+            .unwrap();
+        assert_eq!(
+            take_sent(&mut display),
+            [(0xd1, vec![0x55, 0xaa, 0x66]), (0xd0, vec![0b100, 0x12])]
+        );
+    }
+
+    #[test]
+    fn nvm_write_unkeyed_after_key() {
+        let mut display = display();
+        display.nvm_protection_key(0x0055_aa66).unwrap();
+        display
+            .nvm_write_unkeyed(NvmAddress::Id1, 0x34, NvmWriteToken::confirm())
+            .unwrap();
+        display
+            .nvm_write_unkeyed(NvmAddress::Id2, 0x56, NvmWriteToken::confirm())
+            .unwrap();
+        assert_eq!(
+            take_sent(&mut display),
+            [
+                (0xd1, vec![0x55, 0xaa, 0x66]),
+                (0xd0, vec![0b000, 0x34]),
+                (0xd0, vec![0b001, 0x56]),
+            ]
+        );
+    }
+}
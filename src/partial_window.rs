@@ -0,0 +1,80 @@
+use crate::Ili9341;
+use embedded_graphics_core::{pixelcolor::Rgb565, prelude::*, primitives::Rectangle};
+
+/// A rectangular part of the screen, e.g. a notification bar, that can be
+/// drawn to independently from the rest of the display
+///
+/// Coordinates are relative to the top-left corner of the window and all
+/// drawing is clipped to it. It is obtained with [Ili9341::partial_window].
+pub struct PartialDisplayWindow<'a, IFACE, RESET> {
+    display: &'a mut Ili9341<IFACE, RESET>,
+    area: Rectangle,
+}
+
+impl<IFACE, RESET> Ili9341<IFACE, RESET> {
+    /// Get a [PartialDisplayWindow] covering `rect`, clamped to the screen
+    pub fn partial_window(&mut self, rect: Rectangle) -> PartialDisplayWindow<'_, IFACE, RESET> {
+        let area = rect.intersection(&self.bounding_box());
+        PartialDisplayWindow {
+            display: self,
+            area,
+        }
+    }
+}
+
+impl<IFACE, RESET> OriginDimensions for PartialDisplayWindow<'_, IFACE, RESET> {
+    fn size(&self) -> Size {
+        self.area.size
+    }
+}
+
+impl<IFACE, RESET> DrawTarget for PartialDisplayWindow<'_, IFACE, RESET>
+where
+    IFACE: display_interface::WriteOnlyDataCommand,
+{
+    type Error = display_interface::DisplayError;
+
+    type Color = Rgb565;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let bounding_box = self.bounding_box();
+        let offset = self.area.top_left;
+        self.display.draw_iter(
+            pixels
+                .into_iter()
+                .filter(|Pixel(point, _)| bounding_box.contains(*point))
+                .map(|Pixel(point, color)| Pixel(point + offset, color)),
+        )
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        if &area.intersection(&self.bounding_box()) == area {
+            // All pixels are inside the window
+            let area = Rectangle::new(area.top_left + self.area.top_left, area.size);
+            self.display.fill_contiguous(&area, colors)
+        } else {
+            self.draw_iter(
+                area.points()
+                    .zip(colors)
+                    .map(|(point, color)| Pixel(point, color)),
+            )
+        }
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let area = area.intersection(&self.bounding_box());
+        let area = Rectangle::new(area.top_left + self.area.top_left, area.size);
+        self.display.fill_solid(&area, color)
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        let area = self.area;
+        self.display.fill_solid(&area, color)
+    }
+}
@@ -0,0 +1,513 @@
+use crate::color::rgb666_to_rgb565;
+use crate::{window_size, Command, DisplayError, Ili9341, Result};
+use display_interface::WriteOnlyDataCommand;
+
+/// Interface that can also read data back from the display
+///
+/// `display-interface` only models writes, so interfaces that are wired for
+/// reading from the controller (e.g. SPI with MISO connected) need to
+/// implement this trait to use the methods that query the display state.
+pub trait ReadableInterface: WriteOnlyDataCommand {
+    /// Send the command byte `cmd` and fill `buf` with the parameters
+    /// returned by the display.
+    ///
+    /// Depending on the interface and the command, the display sends a dummy
+    /// clock cycle or a dummy parameter before the actual data. The
+    /// implementation must discard it, so that `buf` only receives the
+    /// parameters documented for `cmd` in the datasheet.
+    fn read_command(&mut self, cmd: u8, buf: &mut [u8]) -> Result;
+
+    /// Access to the clock of the interface, if it can be changed at runtime
+    ///
+    /// The default implementation returns `None`, and the display memory is
+    /// read at whatever frequency the interface is running.
+    fn frequency_hint(&mut self) -> Option<&mut dyn SpiFrequencyHint> {
+        None
+    }
+}
+
+/// Maximum SPI clock frequency for reading from the display
+pub const SPI_READ_MAX_HZ: u32 = 6_670_000;
+/// Maximum SPI clock frequency for writing to the display
+pub const SPI_WRITE_MAX_HZ: u32 = 10_000_000;
+
+/// Interface whose clock frequency can be changed at runtime
+///
+/// Reading the display memory is only reliable up to [SPI_READ_MAX_HZ], so the
+/// driver lowers the frequency while reading and sets it back to the value
+/// returned by [SpiFrequencyHint::frequency_hz] afterwards. When the current
+/// frequency is unknown, restoring it is left to the caller.
+///
+/// Returned by [ReadableInterface::frequency_hint].
+pub trait SpiFrequencyHint {
+    /// Current clock frequency, if known
+    fn frequency_hz(&self) -> Option<u32> {
+        None
+    }
+
+    fn set_frequency_hz(&mut self, hz: u32) -> Result {
+        let _ = hz;
+        Ok(())
+    }
+}
+
+impl<T: SpiFrequencyHint> SpiFrequencyHint for &mut T {
+    fn frequency_hz(&self) -> Option<u32> {
+        (**self).frequency_hz()
+    }
+
+    fn set_frequency_hz(&mut self, hz: u32) -> Result {
+        (**self).set_frequency_hz(hz)
+    }
+}
+
+/// Number of retries of [Ili9341::is_connected]
+pub const DEFAULT_CONNECT_RETRIES: u8 = 2;
+
+/// Number of pixels read from the display memory with each command
+const READ_CHUNK_PIXELS: usize = 16;
+
+/// Whether bit `bit` of `byte` is set
+fn bit(byte: u8, bit: u8) -> bool {
+    byte & (1 << bit) != 0
+}
+
+/// Content of the display power mode register (0Ah), returned by
+/// [Ili9341::read_display_power_mode]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DisplayPowerMode {
+    /// The booster voltage is on
+    pub booster_on: bool,
+    pub idle_mode: bool,
+    pub partial_mode: bool,
+    pub sleep_mode: bool,
+    pub normal_mode: bool,
+    pub display_on: bool,
+}
+
+impl From<u8> for DisplayPowerMode {
+    fn from(byte: u8) -> Self {
+        DisplayPowerMode {
+            booster_on: bit(byte, 7),
+            idle_mode: bit(byte, 6),
+            partial_mode: bit(byte, 5),
+            // The register holds the "sleep out" state
+            sleep_mode: !bit(byte, 4),
+            normal_mode: bit(byte, 3),
+            display_on: bit(byte, 2),
+        }
+    }
+}
+
+/// Content of the display status register (09h), returned by
+/// [Ili9341::read_display_status]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DisplayStatus {
+    /// The booster voltage is on
+    pub booster_on: bool,
+    /// Rows are written bottom to top (MY)
+    pub row_address_order: bool,
+    /// Columns are written right to left (MX)
+    pub column_address_order: bool,
+    /// Rows and columns are exchanged (MV)
+    pub row_column_exchange: bool,
+    /// The panel is refreshed bottom to top (ML)
+    pub vertical_refresh_order: bool,
+    /// Pixels are in BGR order
+    pub bgr: bool,
+    /// The panel is refreshed right to left (MH)
+    pub horizontal_refresh_order: bool,
+    /// Interface pixel format bits, `0b101` for 16 bits and `0b110` for 18
+    /// bits per pixel
+    pub pixel_format: u8,
+    pub idle_mode: bool,
+    pub partial_mode: bool,
+    pub sleep_mode: bool,
+    pub normal_mode: bool,
+    pub vertical_scrolling: bool,
+    pub inverted: bool,
+    pub all_pixels_on: bool,
+    pub all_pixels_off: bool,
+    pub display_on: bool,
+    pub tearing_effect_on: bool,
+    /// Selected gamma curve, 0 to 7
+    pub gamma_curve: u8,
+    /// The tearing effect signal also fires on horizontal blanking
+    pub tearing_effect_hblank: bool,
+}
+
+impl From<[u8; 4]> for DisplayStatus {
+    fn from(bytes: [u8; 4]) -> Self {
+        let [b0, b1, b2, b3] = bytes;
+        DisplayStatus {
+            booster_on: bit(b0, 7),
+            row_address_order: bit(b0, 6),
+            column_address_order: bit(b0, 5),
+            row_column_exchange: bit(b0, 4),
+            vertical_refresh_order: bit(b0, 3),
+            bgr: bit(b0, 2),
+            horizontal_refresh_order: bit(b0, 1),
+            pixel_format: (b1 >> 4) & 0x07,
+            idle_mode: bit(b1, 3),
+            partial_mode: bit(b1, 2),
+            // The register holds the "sleep out" state
+            sleep_mode: !bit(b1, 1),
+            normal_mode: bit(b1, 0),
+            vertical_scrolling: bit(b2, 7),
+            inverted: bit(b2, 5),
+            all_pixels_on: bit(b2, 4),
+            all_pixels_off: bit(b2, 3),
+            display_on: bit(b2, 2),
+            tearing_effect_on: bit(b2, 1),
+            gamma_curve: ((b2 & 0x01) << 2) | (b3 >> 6),
+            tearing_effect_hblank: bit(b3, 5),
+        }
+    }
+}
+
+/// Content of the NV memory status register (D2h), returned by
+/// [Ili9341::read_nvm_status]
+///
+/// Each counter holds how many times the matching register was programmed.
+/// Gamma settings are not stored in the non-volatile memory of the ILI9341,
+/// so there is no counter for them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NvmStatus {
+    /// A write is in progress, the memory is not writable
+    pub busy: bool,
+    pub id1_count: u8,
+    pub id2_count: u8,
+    pub id3_count: u8,
+    /// Programming count of the VCOM offset (VMF)
+    pub vcom_count: u8,
+}
+
+impl NvmStatus {
+    /// Whether the VCOM offset was programmed at least once
+    pub fn vcom_programmed(&self) -> bool {
+        self.vcom_count != 0
+    }
+}
+
+impl From<[u8; 2]> for NvmStatus {
+    fn from(bytes: [u8; 2]) -> Self {
+        let [b0, b1] = bytes;
+        NvmStatus {
+            busy: bit(b1, 7),
+            id1_count: b0 & 0x07,
+            id2_count: (b0 >> 4) & 0x07,
+            id3_count: b1 & 0x07,
+            vcom_count: (b1 >> 4) & 0x07,
+        }
+    }
+}
+
+/// Identification bytes of the display controller, returned by
+/// [Ili9341::read_id]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DisplayId {
+    /// Manufacturer ID (DAh)
+    pub id1: u8,
+    /// Module or driver version ID (DBh)
+    pub id2: u8,
+    /// Module or driver ID (DCh)
+    pub id3: u8,
+}
+
+impl DisplayId {
+    /// Whether the IDs are the ones of a genuine ILI9341
+    ///
+    /// Some clones report different values while being compatible, so a
+    /// mismatch is not necessarily fatal.
+    pub fn is_ili9341(&self) -> bool {
+        (self.id1, self.id2, self.id3) == (0x00, 0x93, 0x41)
+    }
+}
+
+impl<IFACE, RESET> Ili9341<IFACE, RESET>
+where
+    IFACE: ReadableInterface,
+{
+    fn read(&mut self, cmd: Command, buf: &mut [u8]) -> Result {
+        self.interface.read_command(cmd as u8, buf)
+    }
+
+    /// Check the booster voltage bit of the display power mode register
+    ///
+    /// Returns `Ok(())` once the booster is on and the display is ready to
+    /// accept pixel data, and [DisplayError::BusWriteError] otherwise.
+    pub fn poll_display_ready(&mut self) -> Result {
+        let mut power_mode = [0];
+        self.read(Command::ReadDisplayPowerMode, &mut power_mode)?;
+        if power_mode[0] & 0x80 != 0 {
+            Ok(())
+        } else {
+            Err(DisplayError::BusWriteError)
+        }
+    }
+
+    /// Read the display power mode register (0Ah), e.g. to check that
+    /// initialization succeeded
+    pub fn read_display_power_mode(&mut self) -> Result<DisplayPowerMode> {
+        let mut power_mode = [0];
+        self.read(Command::ReadDisplayPowerMode, &mut power_mode)?;
+        Ok(DisplayPowerMode::from(power_mode[0]))
+    }
+
+    /// Read the display status register (09h), which sums up the display
+    /// modes, memory access control and pixel format
+    pub fn read_display_status(&mut self) -> Result<DisplayStatus> {
+        let mut status = [0; 4];
+        self.read(Command::ReadDisplayStatus, &mut status)?;
+        Ok(DisplayStatus::from(status))
+    }
+
+    /// Read the NV memory status (D2h), e.g. to check how many write cycles
+    /// are left before programming the non-volatile memory
+    pub fn read_nvm_status(&mut self) -> Result<NvmStatus> {
+        let mut status = [0; 2];
+        self.read(Command::NvMemoryStatusRead, &mut status)?;
+        Ok(NvmStatus::from(status))
+    }
+
+    /// Read the three ID registers (DAh, DBh and DCh), e.g. to check the
+    /// controller is compatible before drawing anything
+    pub fn read_id(&mut self) -> Result<DisplayId> {
+        let mut id = [[0]; 3];
+        self.read(Command::ReadId1, &mut id[0])?;
+        self.read(Command::ReadId2, &mut id[1])?;
+        self.read(Command::ReadId3, &mut id[2])?;
+        Ok(DisplayId {
+            id1: id[0][0],
+            id2: id[1][0],
+            id3: id[2][0],
+        })
+    }
+
+    /// Read the display identification information (04h), as the 4 bytes
+    /// returned by the display
+    pub fn read_display_id_info(&mut self) -> Result<[u8; 4]> {
+        let mut info = [0; 4];
+        self.read(Command::ReadDisplayIdInfo, &mut info)?;
+        Ok(info)
+    }
+
+    /// Check whether a display answers on the interface, retrying up to
+    /// [DEFAULT_CONNECT_RETRIES] times
+    ///
+    /// See [Ili9341::is_connected_with_retries].
+    pub fn is_connected(&mut self) -> bool {
+        self.is_connected_with_retries(DEFAULT_CONNECT_RETRIES)
+    }
+
+    /// Check whether a display answers on the interface by reading its power
+    /// mode register, retrying up to `retries` times after a failed attempt
+    ///
+    /// A missing display usually reads as all zeros, or all ones when the
+    /// data line has a pull-up, and neither is a valid power mode: after
+    /// initialization some bits are always set, and the two lowest bits are
+    /// always clear.
+    pub fn is_connected_with_retries(&mut self, retries: u8) -> bool {
+        (0..=retries).any(|_| {
+            let mut power_mode = [0];
+            self.read(Command::ReadDisplayPowerMode, &mut power_mode)
+                .is_ok()
+                && power_mode[0] != 0
+                && power_mode[0] & 0x03 == 0
+        })
+    }
+
+    /// Read the memory access control register (MADCTL), which holds the
+    /// orientation, scan order and color order
+    pub fn read_madctl(&mut self) -> Result<u8> {
+        let mut madctl = [0];
+        self.read(Command::ReadDisplayMadctl, &mut madctl)?;
+        Ok(madctl[0])
+    }
+
+    /// Check that MADCTL holds the value last written by the driver, e.g.
+    /// with [Ili9341::set_orientation]
+    ///
+    /// A mismatch points to a communication problem and is reported as
+    /// [DisplayError::BusWriteError]. The two lowest bits are not used by
+    /// the display and are not compared.
+    pub fn verify_madctl(&mut self) -> Result {
+        if (self.read_madctl()? ^ self.madctl) & 0xfc != 0 {
+            return Err(DisplayError::BusWriteError);
+        }
+        Ok(())
+    }
+
+    /// Read the display brightness (52h), as set with
+    /// [Ili9341::set_brightness]
+    pub fn read_brightness(&mut self) -> Result<u8> {
+        let mut brightness = [0];
+        self.read(Command::ReadBrightness, &mut brightness)?;
+        Ok(brightness[0])
+    }
+
+    /// Read the scanline the display is currently refreshing
+    ///
+    /// Polling this before writing pixels allows tear-free updates without
+    /// a TE pin, but each read adds a full command round-trip, so it is far
+    /// less precise than waiting on the tearing effect signal.
+    pub fn read_scanline(&mut self) -> Result<u16> {
+        let mut scanline = [0; 2];
+        self.read(Command::GetScanline, &mut scanline)?;
+        Ok(u16::from_be_bytes(scanline) & 0x03ff)
+    }
+
+    /// Same as [Ili9341::read_scanline]
+    pub fn get_scanline(&mut self) -> Result<u16> {
+        self.read_scanline()
+    }
+}
+
+impl<IFACE, RESET> Ili9341<IFACE, RESET>
+where
+    IFACE: ReadableInterface,
+{
+    /// Read back a rectangle of the display memory, represented by top-left
+    /// corner (x0, y0) and bottom-right corner (x1, y1), as rgb565 values
+    ///
+    /// `buf` is filled in the same order pixels are drawn. Reading stops when
+    /// `buf` is full or the whole rectangle has been read.
+    ///
+    /// If the interface provides a [SpiFrequencyHint], the clock is lowered to
+    /// [SPI_READ_MAX_HZ] while reading and restored afterwards.
+    pub fn read_frame_partial(
+        &mut self,
+        x0: u16,
+        y0: u16,
+        x1: u16,
+        y1: u16,
+        buf: &mut [u16],
+    ) -> Result {
+        let previous_hz = match self.interface.frequency_hint() {
+            Some(hint) => {
+                let previous_hz = hint.frequency_hz();
+                hint.set_frequency_hz(SPI_READ_MAX_HZ)?;
+                previous_hz
+            }
+            None => None,
+        };
+        let result = self.read_memory(x0, y0, x1, y1, buf);
+        if let (Some(hz), Some(hint)) = (previous_hz, self.interface.frequency_hint()) {
+            hint.set_frequency_hz(hz)?;
+        }
+        result
+    }
+
+    /// Read back the rgb565 value of a single pixel
+    pub fn read_pixel(&mut self, x: u16, y: u16) -> Result<u16> {
+        let mut pixel = [0];
+        self.read_frame_partial(x, y, x, y, &mut pixel)?;
+        Ok(pixel[0])
+    }
+
+    /// Flip a rectangle, represented by top-left corner (x0, y0) and
+    /// bottom-right corner (x1, y1), horizontally
+    ///
+    /// The rectangle is read into `buf`, which must hold at least all of its
+    /// pixels, mirrored and written back.
+    pub fn mirror_region_h(
+        &mut self,
+        x0: u16,
+        y0: u16,
+        x1: u16,
+        y1: u16,
+        buf: &mut [u16],
+    ) -> Result {
+        let width = usize::from(x1.saturating_sub(x0)) + 1;
+        self.transform_region(x0, y0, x1, y1, buf, |pixels| {
+            pixels.chunks_exact_mut(width).for_each(<[u16]>::reverse)
+        })
+    }
+
+    /// Flip a rectangle, represented by top-left corner (x0, y0) and
+    /// bottom-right corner (x1, y1), vertically
+    ///
+    /// The rectangle is read into `buf`, which must hold at least all of its
+    /// pixels, mirrored and written back.
+    pub fn mirror_region_v(
+        &mut self,
+        x0: u16,
+        y0: u16,
+        x1: u16,
+        y1: u16,
+        buf: &mut [u16],
+    ) -> Result {
+        let width = usize::from(x1.saturating_sub(x0)) + 1;
+        self.transform_region(x0, y0, x1, y1, buf, |pixels| {
+            // Reversing all the pixels flips both ways, undo the horizontal flip
+            pixels.reverse();
+            pixels.chunks_exact_mut(width).for_each(<[u16]>::reverse)
+        })
+    }
+
+    fn transform_region<F>(
+        &mut self,
+        x0: u16,
+        y0: u16,
+        x1: u16,
+        y1: u16,
+        buf: &mut [u16],
+        transform: F,
+    ) -> Result
+    where
+        F: FnOnce(&mut [u16]),
+    {
+        let pixels = window_size(x0, y0, x1, y1)?;
+        let buf = buf
+            .get_mut(..pixels)
+            .ok_or(DisplayError::InvalidFormatError)?;
+        self.read_frame_partial(x0, y0, x1, y1, buf)?;
+        transform(buf);
+        self.draw_raw_slice(x0, y0, x1, y1, buf)
+    }
+
+    fn read_memory(&mut self, x0: u16, y0: u16, x1: u16, y1: u16, buf: &mut [u16]) -> Result {
+        let len = buf.len().min(window_size(x0, y0, x1, y1)?);
+        self.set_window(x0, y0, x1, y1)?;
+
+        // Memory reads always return 3 bytes per pixel
+        let mut bytes = [0; 3 * READ_CHUNK_PIXELS];
+        let mut cmd = Command::MemoryRead;
+        for chunk in buf[..len].chunks_mut(READ_CHUNK_PIXELS) {
+            let bytes = &mut bytes[..3 * chunk.len()];
+            self.read(cmd, bytes)?;
+            for (pixel, rgb) in chunk.iter_mut().zip(bytes.chunks_exact(3)) {
+                *pixel = rgb666_to_rgb565(rgb[0], rgb[1], rgb[2]);
+            }
+            cmd = Command::ReadMemoryContinue;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::SPI_READ_MAX_HZ;
+    use crate::mock::display;
+    use std::vec;
+
+    #[test]
+    fn read_restores_frequency() {
+        let mut display = display();
+        display.interface_mut().frequency_hz = Some(24_000_000);
+        display.read_pixel(0, 0).unwrap();
+        assert_eq!(
+            display.interface_mut().frequencies,
+            [SPI_READ_MAX_HZ, 24_000_000]
+        );
+    }
+
+    #[test]
+    fn read_without_frequency_hint() {
+        let mut display = display();
+        assert_eq!(display.read_pixel(0, 0).ok(), Some(0));
+        assert_eq!(display.interface_mut().frequencies, vec![]);
+    }
+}
@@ -0,0 +1,127 @@
+use display_interface::{DataFormat, DisplayError, WriteOnlyDataCommand};
+use heapless::Vec;
+
+/// Maximum number of commands kept by a [CommandRecorder]
+pub const MAX_RECORDED_COMMANDS: usize = 64;
+/// Maximum number of data bytes kept for each [RecordedCommand]
+pub const MAX_RECORDED_DATA: usize = 16;
+
+/// A command byte and the data bytes that were sent after it
+#[derive(Clone, Debug)]
+pub struct RecordedCommand {
+    pub cmd: u8,
+    pub data: Vec<u8, MAX_RECORDED_DATA>,
+    /// Set when more data was sent than could be recorded
+    pub truncated: bool,
+}
+
+/// Interface that records every command and its arguments instead of
+/// sending them to a display, to debug initialization sequences
+///
+/// ```ignore
+/// let mut rec = CommandRecorder::new();
+/// Ili9341::new(&mut rec, reset, &mut delay, Orientation::Portrait, DisplaySize240x320)?;
+/// for command in rec.commands() {
+///     log::info!("{:02x} {:02x?}", command.cmd, command.data);
+/// }
+/// rec.replay(&mut real_iface)?;
+/// ```
+///
+/// Recording never fails: commands beyond [MAX_RECORDED_COMMANDS] and data
+/// beyond [MAX_RECORDED_DATA] bytes per command are dropped, which is
+/// reported by [CommandRecorder::overflowed] and [RecordedCommand::truncated].
+#[derive(Default)]
+pub struct CommandRecorder {
+    commands: Vec<RecordedCommand, MAX_RECORDED_COMMANDS>,
+    overflowed: bool,
+}
+
+impl CommandRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Commands recorded so far, in the order they were sent
+    pub fn commands(&self) -> &[RecordedCommand] {
+        &self.commands
+    }
+
+    /// Whether some commands were dropped because the recorder was full
+    pub fn overflowed(&self) -> bool {
+        self.overflowed
+    }
+
+    pub fn clear(&mut self) {
+        self.commands.clear();
+        self.overflowed = false;
+    }
+
+    /// Send the recorded commands and data to another interface
+    pub fn replay<IFACE: WriteOnlyDataCommand>(
+        &self,
+        iface: &mut IFACE,
+    ) -> Result<(), DisplayError> {
+        for command in &self.commands {
+            iface.send_commands(DataFormat::U8(&[command.cmd]))?;
+            iface.send_data(DataFormat::U8(&command.data))?;
+        }
+        Ok(())
+    }
+
+    fn record_command(&mut self, cmd: u8) {
+        let command = RecordedCommand {
+            cmd,
+            data: Vec::new(),
+            truncated: false,
+        };
+        if self.commands.push(command).is_err() {
+            self.overflowed = true;
+        }
+    }
+
+    fn record_data(&mut self, byte: u8) {
+        if self.overflowed {
+            return;
+        }
+        if let Some(command) = self.commands.last_mut() {
+            if command.data.push(byte).is_err() {
+                command.truncated = true;
+            }
+        }
+    }
+}
+
+/// Call `f` with every byte of `data`, in the order they would be sent
+fn for_each_byte<F: FnMut(u8)>(data: DataFormat<'_>, f: F) -> Result<(), DisplayError> {
+    match data {
+        DataFormat::U8(buf) => buf.iter().copied().for_each(f),
+        DataFormat::U16(buf) => buf.iter().flat_map(|w| w.to_ne_bytes()).for_each(f),
+        DataFormat::U16BE(buf) => buf.iter().flat_map(|w| w.to_be_bytes()).for_each(f),
+        DataFormat::U16LE(buf) => buf.iter().flat_map(|w| w.to_le_bytes()).for_each(f),
+        DataFormat::U8Iter(iter) => iter.for_each(f),
+        DataFormat::U16BEIter(iter) => iter.flat_map(u16::to_be_bytes).for_each(f),
+        DataFormat::U16LEIter(iter) => iter.flat_map(u16::to_le_bytes).for_each(f),
+        _ => return Err(DisplayError::DataFormatNotImplemented),
+    }
+    Ok(())
+}
+
+impl WriteOnlyDataCommand for CommandRecorder {
+    fn send_commands(&mut self, cmd: DataFormat<'_>) -> Result<(), DisplayError> {
+        for_each_byte(cmd, |cmd| self.record_command(cmd))
+    }
+
+    fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        for_each_byte(buf, |byte| self.record_data(byte))
+    }
+}
+
+impl WriteOnlyDataCommand for &mut CommandRecorder {
+    fn send_commands(&mut self, cmd: DataFormat<'_>) -> Result<(), DisplayError> {
+        (**self).send_commands(cmd)
+    }
+
+    fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        (**self).send_data(buf)
+    }
+}
@@ -0,0 +1,235 @@
+use crate::{window_size, Ili9341, Result};
+use display_interface::WriteOnlyDataCommand;
+
+impl<IFACE, RESET> Ili9341<IFACE, RESET>
+where
+    IFACE: WriteOnlyDataCommand,
+{
+    /// Fill the rectangle with corners (x0, y0) and (x1, y1), clipped to the
+    /// screen
+    pub(crate) fn fill_clipped(
+        &mut self,
+        x0: i32,
+        y0: i32,
+        x1: i32,
+        y1: i32,
+        color: u16,
+    ) -> Result {
+        let x0 = x0.max(0);
+        let y0 = y0.max(0);
+        let x1 = x1.min(self.width() as i32 - 1);
+        let y1 = y1.min(self.height() as i32 - 1);
+        if x1 < x0 || y1 < y0 {
+            return Ok(());
+        }
+        let pixels = (x1 - x0 + 1) as usize * (y1 - y0 + 1) as usize;
+        self.draw_raw_iter(
+            x0 as u16,
+            y0 as u16,
+            x1 as u16,
+            y1 as u16,
+            core::iter::repeat(color).take(pixels),
+        )
+    }
+
+    /// Set a single pixel, ignoring pixels outside of the screen
+    pub fn draw_pixel(&mut self, x: u16, y: u16, color: u16) -> Result {
+        let (x, y) = (i32::from(x), i32::from(y));
+        self.fill_clipped(x, y, x, y, color)
+    }
+
+    /// Draw a horizontal line from (x0, y) to (x1, y), clipped to the screen
+    pub fn draw_hline(&mut self, x0: u16, y: u16, x1: u16, color: u16) -> Result {
+        let (x0, x1) = (x0.min(x1), x0.max(x1));
+        self.fill_clipped(x0.into(), y.into(), x1.into(), y.into(), color)
+    }
+
+    /// Draw a vertical line from (x, y0) to (x, y1), clipped to the screen
+    pub fn draw_vline(&mut self, x: u16, y0: u16, y1: u16, color: u16) -> Result {
+        let (y0, y1) = (y0.min(y1), y0.max(y1));
+        self.fill_clipped(x.into(), y0.into(), x.into(), y1.into(), color)
+    }
+
+    /// Fill a rectangle, represented by top-left corner (x0, y0) and
+    /// bottom-right corner (x1, y1), with `color`, clipped to the screen
+    pub fn fill_rect(&mut self, x0: u16, y0: u16, x1: u16, y1: u16, color: u16) -> Result {
+        window_size(x0, y0, x1, y1)?;
+        self.fill_clipped(x0.into(), y0.into(), x1.into(), y1.into(), color)
+    }
+
+    /// Draw a horizontal progress bar of `width` x `height` pixels with its
+    /// top-left corner at (x, y)
+    ///
+    /// The left `percent` of the bar, clamped to 100, is filled with `fg` and
+    /// the rest with `bg`. With `Some(border)` a 1 pixel frame of that color
+    /// is drawn around the bar, inside the given size.
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_progress_bar(
+        &mut self,
+        x: u16,
+        y: u16,
+        width: u16,
+        height: u16,
+        percent: u8,
+        fg: u16,
+        bg: u16,
+        border: Option<u16>,
+    ) -> Result {
+        if width == 0 || height == 0 {
+            return Ok(());
+        }
+        let (x0, y0) = (i32::from(x), i32::from(y));
+        let (x1, y1) = (x0 + i32::from(width) - 1, y0 + i32::from(height) - 1);
+
+        let inset = if let Some(border) = border {
+            self.fill_clipped(x0, y0, x1, y0, border)?;
+            self.fill_clipped(x0, y1, x1, y1, border)?;
+            self.fill_clipped(x0, y0, x0, y1, border)?;
+            self.fill_clipped(x1, y0, x1, y1, border)?;
+            1
+        } else {
+            0
+        };
+        let (x0, y0, x1, y1) = (x0 + inset, y0 + inset, x1 - inset, y1 - inset);
+        if x1 < x0 || y1 < y0 {
+            return Ok(());
+        }
+
+        let filled = (x1 - x0 + 1) * i32::from(percent.min(100)) / 100;
+        self.fill_clipped(x0, y0, x0 + filled - 1, y1, fg)?;
+        self.fill_clipped(x0 + filled, y0, x1, y1, bg)
+    }
+
+    /// Draw the outline of a circle with center (cx, cy) and radius `r`,
+    /// clipped to the screen
+    ///
+    /// Uses the midpoint circle algorithm, which needs no multiplication
+    /// or floating point. Consecutive points of an octant in the same column
+    /// or row are sent as a single span.
+    pub fn draw_circle(&mut self, cx: u16, cy: u16, r: u16, color: u16) -> Result {
+        let (cx, cy) = (i32::from(cx), i32::from(cy));
+        let mut points = MidpointCircle::new(r).peekable();
+        while let Some((x, y0)) = points.next() {
+            let mut y1 = y0;
+            while let Some((_, y)) = points.next_if(|&(next_x, _)| next_x == x) {
+                y1 = y;
+            }
+            // Columns cx ± x, in the octants next to the horizontal axis
+            self.fill_clipped(cx + x, cy + y0, cx + x, cy + y1, color)?;
+            self.fill_clipped(cx - x, cy + y0, cx - x, cy + y1, color)?;
+            self.fill_clipped(cx + x, cy - y1, cx + x, cy - y0, color)?;
+            self.fill_clipped(cx - x, cy - y1, cx - x, cy - y0, color)?;
+            // Rows cy ± x, in the octants next to the vertical axis
+            self.fill_clipped(cx + y0, cy + x, cx + y1, cy + x, color)?;
+            self.fill_clipped(cx - y1, cy + x, cx - y0, cy + x, color)?;
+            self.fill_clipped(cx + y0, cy - x, cx + y1, cy - x, color)?;
+            self.fill_clipped(cx - y1, cy - x, cx - y0, cy - x, color)?;
+        }
+        Ok(())
+    }
+
+    /// Draw a filled circle with center (cx, cy) and radius `r`, clipped to
+    /// the screen, using one horizontal span per row
+    pub fn draw_filled_circle(&mut self, cx: u16, cy: u16, r: u16, color: u16) -> Result {
+        let (cx, cy) = (i32::from(cx), i32::from(cy));
+        let mut points = MidpointCircle::new(r).peekable();
+        while let Some((x, y)) = points.next() {
+            self.fill_clipped(cx - x, cy + y, cx + x, cy + y, color)?;
+            if y != 0 {
+                self.fill_clipped(cx - x, cy - y, cx + x, cy - y, color)?;
+            }
+            // Rows cy ± x are reached again with a wider span while x doesn't
+            // change, only draw the last one
+            let x_changes = points.peek().map_or(true, |&(next_x, _)| next_x != x);
+            if x > y && x_changes {
+                self.fill_clipped(cx - y, cy + x, cx + y, cy + x, color)?;
+                self.fill_clipped(cx - y, cy - x, cx + y, cy - x, color)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Points (x, y) of the first octant of a circle centered on the origin,
+/// from (r, 0) to the diagonal
+struct MidpointCircle {
+    x: i32,
+    y: i32,
+    err: i32,
+}
+
+impl MidpointCircle {
+    fn new(r: u16) -> Self {
+        let r = i32::from(r);
+        MidpointCircle {
+            x: r,
+            y: 0,
+            err: 1 - r,
+        }
+    }
+}
+
+impl Iterator for MidpointCircle {
+    type Item = (i32, i32);
+
+    fn next(&mut self) -> Option<(i32, i32)> {
+        if self.x < self.y {
+            return None;
+        }
+        let point = (self.x, self.y);
+        self.y += 1;
+        if self.err < 0 {
+            self.err += 2 * self.y + 1;
+        } else {
+            self.x -= 1;
+            self.err += 2 * (self.y - self.x) + 1;
+        }
+        Some(point)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::MidpointCircle;
+    use crate::mock::{display, take_sent};
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn circle_spans() {
+        let mut display = display();
+        display.draw_circle(100, 100, 40, 0xffff).unwrap();
+        let sent = take_sent(&mut display);
+
+        let mut drawn = BTreeSet::new();
+        let mut spans = 0;
+        for window in sent.chunks(3) {
+            let [(0x2a, columns), (0x2b, rows), (0x2c, _)] = window else {
+                panic!("unexpected commands {:?}", window);
+            };
+            let coord = |bytes: &[u8]| i32::from(u16::from_be_bytes([bytes[0], bytes[1]]));
+            let (x0, x1) = (coord(&columns[..2]), coord(&columns[2..]));
+            let (y0, y1) = (coord(&rows[..2]), coord(&rows[2..]));
+            assert!(x0 == x1 || y0 == y1, "span is not a line");
+            for x in x0..=x1 {
+                for y in y0..=y1 {
+                    drawn.insert((x, y));
+                }
+            }
+            spans += 1;
+        }
+
+        let mut expected = BTreeSet::new();
+        for (x, y) in MidpointCircle::new(40) {
+            for (dx, dy) in [(x, y), (y, x)] {
+                for (sx, sy) in [(1, 1), (-1, 1), (1, -1), (-1, -1)] {
+                    expected.insert((100 + sx * dx, 100 + sy * dy));
+                }
+            }
+        }
+        assert_eq!(drawn, expected);
+        // One command per pixel would take as many spans as points
+        assert!(spans < expected.len() / 2, "{} spans", spans);
+    }
+}
@@ -0,0 +1,61 @@
+use core::fmt;
+
+/// Fixed capacity string on the stack, to format values without `alloc`
+///
+/// Writing more than `N` bytes fails with [fmt::Error], keeping what was
+/// written before.
+///
+/// ```ignore
+/// use core::fmt::Write;
+///
+/// let mut s = SmallString::<16>::new();
+/// write!(s, "{} rpm", rpm)?;
+/// display.draw_string_fast(0, 0, s.as_str(), &atlas, bg)?;
+/// ```
+#[derive(Clone, Copy)]
+pub struct SmallString<const N: usize> {
+    buf: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> SmallString<N> {
+    pub const fn new() -> Self {
+        SmallString {
+            buf: [0; N],
+            len: 0,
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        // Only whole `&str`s are ever copied into `buf`
+        core::str::from_utf8(&self.buf[..self.len]).unwrap_or_default()
+    }
+
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+}
+
+impl<const N: usize> Default for SmallString<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> fmt::Write for SmallString<N> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        if end > N {
+            return Err(fmt::Error);
+        }
+        self.buf[self.len..end].copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+impl<const N: usize> fmt::Debug for SmallString<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
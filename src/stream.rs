@@ -0,0 +1,113 @@
+use crate::{Command, Ili9341, Result};
+#[cfg(feature = "embedded-io")]
+use display_interface::DisplayError;
+use display_interface::{DataFormat, WriteOnlyDataCommand};
+
+/// Size of the chunks [RawPixelWriter] passes to the interface
+const STREAM_CHUNK_BYTES: usize = 512;
+
+/// Byte oriented writer to the display memory, returned by
+/// [Ili9341::raw_pixel_writer]
+///
+/// Bytes are rgb565 pixels, most significant byte first, filling the screen
+/// row by row from the top-left corner. This allows piping the output of a
+/// decoder to the display without an intermediate frame buffer.
+pub struct RawPixelWriter<'a, IFACE, RESET> {
+    display: &'a mut Ili9341<IFACE, RESET>,
+}
+
+impl<IFACE, RESET> RawPixelWriter<'_, IFACE, RESET>
+where
+    IFACE: WriteOnlyDataCommand,
+{
+    /// Send all of `bytes`, continuing where the previous write stopped
+    pub fn write_all(&mut self, bytes: &[u8]) -> Result {
+        for chunk in bytes.chunks(STREAM_CHUNK_BYTES) {
+            self.display.interface.send_data(DataFormat::U8(chunk))?;
+        }
+        Ok(())
+    }
+}
+
+impl<IFACE, RESET> Ili9341<IFACE, RESET>
+where
+    IFACE: WriteOnlyDataCommand,
+{
+    /// Set the window to the whole display and start a memory write, then
+    /// return a writer that sends raw pixel bytes
+    ///
+    /// The pixel format must be [PixelFormat::Bits16](crate::PixelFormat::Bits16),
+    /// since bytes are sent unchanged.
+    pub fn raw_pixel_writer(&mut self) -> Result<RawPixelWriter<'_, IFACE, RESET>> {
+        let x1 = self.width.saturating_sub(1).min(u16::MAX as usize) as u16;
+        let y1 = self.height.saturating_sub(1).min(u16::MAX as usize) as u16;
+        self.set_window(0, 0, x1, y1)?;
+        self.command(Command::MemoryWrite, &[])?;
+        Ok(RawPixelWriter { display: self })
+    }
+}
+
+/// Error of the [embedded_io::Write] implementation of [RawPixelWriter]
+#[cfg(feature = "embedded-io")]
+#[derive(Debug)]
+pub struct RawPixelWriterError(pub DisplayError);
+
+#[cfg(feature = "embedded-io")]
+impl embedded_io::Error for RawPixelWriterError {
+    fn kind(&self) -> embedded_io::ErrorKind {
+        embedded_io::ErrorKind::Other
+    }
+}
+
+#[cfg(feature = "embedded-io")]
+impl<IFACE, RESET> embedded_io::ErrorType for RawPixelWriter<'_, IFACE, RESET> {
+    type Error = RawPixelWriterError;
+}
+
+#[cfg(feature = "embedded-io")]
+impl<IFACE, RESET> embedded_io::Write for RawPixelWriter<'_, IFACE, RESET>
+where
+    IFACE: WriteOnlyDataCommand,
+{
+    fn write(&mut self, buf: &[u8]) -> core::result::Result<usize, Self::Error> {
+        let chunk = &buf[..buf.len().min(STREAM_CHUNK_BYTES)];
+        self.display
+            .interface
+            .send_data(DataFormat::U8(chunk))
+            .map_err(RawPixelWriterError)?;
+        Ok(chunk.len())
+    }
+
+    /// Bytes are passed to the interface as they are written, so there is
+    /// nothing to flush
+    fn flush(&mut self) -> core::result::Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "embedded-io"))]
+mod tests {
+    extern crate std;
+
+    use super::STREAM_CHUNK_BYTES;
+    use crate::mock::{display, take_sent};
+    use embedded_io::Write;
+    use std::vec;
+
+    #[test]
+    fn write_returns_accepted_bytes() {
+        let mut display = display();
+        let bytes = [0xa5; STREAM_CHUNK_BYTES + 4];
+        {
+            let mut writer = display.raw_pixel_writer().unwrap();
+            assert_eq!(writer.write(&bytes).unwrap(), STREAM_CHUNK_BYTES);
+            assert_eq!(writer.write(&[]).unwrap(), 0);
+            writer.write_all(&bytes).unwrap();
+            writer.flush().unwrap();
+        }
+        let sent = take_sent(&mut display);
+        let (cmd, data) = sent.last().unwrap();
+        assert_eq!(*cmd, 0x2c);
+        assert_eq!(*data, vec![0xa5; 2 * STREAM_CHUNK_BYTES + 4]);
+    }
+}
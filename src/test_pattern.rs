@@ -0,0 +1,88 @@
+use crate::color::rgb888_to_rgb565;
+use crate::{Ili9341, Result};
+use display_interface::WriteOnlyDataCommand;
+
+/// Fixed patterns useful to check a display during hardware bring-up
+pub enum TestPattern {
+    /// 8 vertical bars: red, green, blue, cyan, magenta, yellow, white, black
+    ColorBars,
+    /// Horizontal black to white gradient
+    Gradient,
+    /// Black and white checkerboard of 10x10 pixel squares
+    Checkerboard,
+    /// Whole screen white
+    White,
+    /// Whole screen black
+    Black,
+}
+
+const WHITE: u16 = 0xffff;
+const BLACK: u16 = 0x0000;
+
+const COLOR_BARS: [u16; 8] = [
+    0xf800, // red
+    0x07e0, // green
+    0x001f, // blue
+    0x07ff, // cyan
+    0xf81f, // magenta
+    0xffe0, // yellow
+    WHITE,  // white
+    BLACK,  // black
+];
+
+const CHECKERBOARD_SQUARE: usize = 10;
+
+impl<IFACE, RESET> Ili9341<IFACE, RESET>
+where
+    IFACE: WriteOnlyDataCommand,
+{
+    /// Fill the whole screen with one of the [TestPattern]s
+    pub fn draw_test_pattern(&mut self, pattern: TestPattern) -> Result {
+        let (width, height) = (self.width(), self.height());
+        if width == 0 || height == 0 {
+            return Ok(());
+        }
+        let x1 = (width - 1) as u16;
+        let y1 = (height - 1) as u16;
+
+        match pattern {
+            TestPattern::ColorBars => {
+                let bars = COLOR_BARS.len();
+                for (i, &color) in COLOR_BARS.iter().enumerate() {
+                    let bar_x0 = i * width / bars;
+                    let bar_x1 = (i + 1) * width / bars;
+                    if bar_x1 == bar_x0 {
+                        continue;
+                    }
+                    let pixels = core::iter::repeat(color).take((bar_x1 - bar_x0) * height);
+                    self.draw_raw_iter(bar_x0 as u16, 0, (bar_x1 - 1) as u16, y1, pixels)?;
+                }
+                Ok(())
+            }
+            TestPattern::Gradient => {
+                let max_x = (width - 1).max(1);
+                let pixels = (0..height).flat_map(|_| {
+                    (0..width).map(move |x| {
+                        let level = (x * 0xff / max_x) as u8;
+                        rgb888_to_rgb565(level, level, level)
+                    })
+                });
+                self.draw_raw_iter(0, 0, x1, y1, pixels)
+            }
+            TestPattern::Checkerboard => {
+                let pixels = (0..height).flat_map(|y| {
+                    (0..width).map(move |x| {
+                        if (x / CHECKERBOARD_SQUARE + y / CHECKERBOARD_SQUARE) % 2 == 0 {
+                            WHITE
+                        } else {
+                            BLACK
+                        }
+                    })
+                });
+                self.draw_raw_iter(0, 0, x1, y1, pixels)
+            }
+            TestPattern::White => self.clear_screen(WHITE),
+            TestPattern::Black => self.clear_screen(BLACK),
+        }
+    }
+}
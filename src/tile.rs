@@ -0,0 +1,84 @@
+use crate::{DisplayError, Ili9341, Result};
+use display_interface::WriteOnlyDataCommand;
+
+/// Draws a grid of `TILE_W`x`TILE_H` tiles, e.g. for tile based games
+///
+/// `map` holds one index into `tiles` per cell, row by row, with
+/// `map_width` cells per row. Every tile holds `TILE_W * TILE_H` rgb565
+/// pixels, row by row.
+pub struct TileRenderer<'a, const TILE_W: u16, const TILE_H: u16> {
+    tiles: &'a [&'a [u16]],
+    map: &'a [u8],
+    map_width: u16,
+}
+
+impl<'a, const TILE_W: u16, const TILE_H: u16> TileRenderer<'a, TILE_W, TILE_H> {
+    pub fn new(tiles: &'a [&'a [u16]], map: &'a [u8], map_width: u16) -> Self {
+        TileRenderer {
+            tiles,
+            map,
+            map_width,
+        }
+    }
+
+    /// Draw the map with its top-left corner at (x0, y0)
+    ///
+    /// Tiles are clipped to the screen, and tiles completely outside of it
+    /// are skipped. Returns [DisplayError::OutOfBoundsError] if the map
+    /// references a missing tile, or [DisplayError::InvalidFormatError] if a
+    /// tile doesn't have `TILE_W * TILE_H` pixels.
+    pub fn draw_tile_map<IFACE, RESET>(
+        &self,
+        display: &mut Ili9341<IFACE, RESET>,
+        x0: u16,
+        y0: u16,
+    ) -> Result
+    where
+        IFACE: WriteOnlyDataCommand,
+    {
+        if self.map_width == 0 || TILE_W == 0 || TILE_H == 0 {
+            return Ok(());
+        }
+        let (width, height) = (display.width(), display.height());
+        let tile_w = usize::from(TILE_W);
+        let tile_h = usize::from(TILE_H);
+
+        for (i, &index) in self.map.iter().enumerate() {
+            let column = i % usize::from(self.map_width);
+            let row = i / usize::from(self.map_width);
+            let x = usize::from(x0) + column * tile_w;
+            let y = usize::from(y0) + row * tile_h;
+            if y >= height {
+                break;
+            }
+            if x >= width {
+                continue;
+            }
+
+            let tile = self
+                .tiles
+                .get(usize::from(index))
+                .ok_or(DisplayError::OutOfBoundsError)?;
+            if tile.len() != tile_w * tile_h {
+                return Err(DisplayError::InvalidFormatError);
+            }
+
+            let visible_w = tile_w.min(width - x);
+            let visible_h = tile_h.min(height - y);
+            let (x, y) = (x as u16, y as u16);
+            let x1 = x + visible_w as u16 - 1;
+
+            if visible_w == tile_w {
+                // The tile is only clipped at the bottom, if at all
+                let y1 = y + visible_h as u16 - 1;
+                display.draw_raw_slice(x, y, x1, y1, &tile[..tile_w * visible_h])?;
+            } else {
+                for (dy, tile_row) in tile.chunks_exact(tile_w).take(visible_h).enumerate() {
+                    let y = y + dy as u16;
+                    display.draw_raw_slice(x, y, x1, y, &tile_row[..visible_w])?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
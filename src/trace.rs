@@ -0,0 +1,48 @@
+use crate::Ili9341;
+
+/// Events reported to a [TraceHook]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TraceEvent {
+    /// [Ili9341::draw_raw_iter] was called for a window of `pixel_count` pixels
+    DrawRawStart { pixel_count: u32, timestamp_us: u64 },
+    /// The pixels of the last [TraceEvent::DrawRawStart] have been sent
+    DrawRawEnd { timestamp_us: u64 },
+}
+
+/// Callbacks used to record a performance trace of the driver
+///
+/// Both are plain functions, so they typically write to a static ring buffer
+/// and read a free-running hardware timer.
+#[derive(Clone, Copy)]
+pub struct TraceHook {
+    /// Current time in microseconds
+    pub now_us: fn() -> u64,
+    /// Record an event
+    pub record: fn(TraceEvent),
+}
+
+impl<IFACE, RESET> Ili9341<IFACE, RESET> {
+    /// Install a hook that is called around every [Ili9341::draw_raw_iter]
+    pub fn set_trace_hook(&mut self, hook: Option<TraceHook>) {
+        self.trace_hook = hook;
+    }
+
+    pub(crate) fn trace_draw_start(&self, x0: u16, y0: u16, x1: u16, y1: u16) {
+        if let Some(hook) = &self.trace_hook {
+            let width = u32::from(x1.saturating_sub(x0)) + 1;
+            let height = u32::from(y1.saturating_sub(y0)) + 1;
+            (hook.record)(TraceEvent::DrawRawStart {
+                pixel_count: width * height,
+                timestamp_us: (hook.now_us)(),
+            });
+        }
+    }
+
+    pub(crate) fn trace_draw_end(&self) {
+        if let Some(hook) = &self.trace_hook {
+            (hook.record)(TraceEvent::DrawRawEnd {
+                timestamp_us: (hook.now_us)(),
+            });
+        }
+    }
+}
@@ -0,0 +1,70 @@
+/// A rectangle of the display, represented by top-left corner (x0, y0) and
+/// bottom-right corner (x1, y1), both included
+///
+/// It can be built from a `(x0, y0, x1, y1)` tuple, or from an
+/// embedded-graphics `Rectangle` with the `graphics-core` feature, and passed
+/// to [Ili9341::draw_raw_iter_window](crate::Ili9341::draw_raw_iter_window) and
+/// [Ili9341::draw_raw_slice_window](crate::Ili9341::draw_raw_slice_window).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DisplayWindow {
+    pub x0: u16,
+    pub y0: u16,
+    pub x1: u16,
+    pub y1: u16,
+}
+
+impl DisplayWindow {
+    /// Number of columns, 0 if the corners are swapped
+    pub fn width(&self) -> u16 {
+        if self.x1 < self.x0 {
+            return 0;
+        }
+        (self.x1 - self.x0).saturating_add(1)
+    }
+
+    /// Number of rows, 0 if the corners are swapped
+    pub fn height(&self) -> u16 {
+        if self.y1 < self.y0 {
+            return 0;
+        }
+        (self.y1 - self.y0).saturating_add(1)
+    }
+
+    /// Number of pixels, 0 if the corners are swapped
+    pub fn pixel_count(&self) -> u32 {
+        if self.x1 < self.x0 || self.y1 < self.y0 {
+            return 0;
+        }
+        (u32::from(self.x1 - self.x0) + 1) * (u32::from(self.y1 - self.y0) + 1)
+    }
+}
+
+impl From<(u16, u16, u16, u16)> for DisplayWindow {
+    fn from((x0, y0, x1, y1): (u16, u16, u16, u16)) -> Self {
+        DisplayWindow { x0, y0, x1, y1 }
+    }
+}
+
+/// Coordinates are clamped to the `u16` range. A zero sized rectangle gives
+/// a window with swapped corners, which drawing methods reject.
+#[cfg(feature = "graphics-core")]
+impl From<embedded_graphics_core::primitives::Rectangle> for DisplayWindow {
+    fn from(rect: embedded_graphics_core::primitives::Rectangle) -> Self {
+        let clamp = |v: i32| v.clamp(0, i32::from(u16::MAX)) as u16;
+        let (x0, y0) = (rect.top_left.x, rect.top_left.y);
+        match rect.bottom_right() {
+            Some(br) => DisplayWindow {
+                x0: clamp(x0),
+                y0: clamp(y0),
+                x1: clamp(br.x),
+                y1: clamp(br.y),
+            },
+            None => DisplayWindow {
+                x0: 1,
+                y0: 1,
+                x1: 0,
+                y1: 0,
+            },
+        }
+    }
+}